@@ -0,0 +1,393 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+// A signed integer of any size for when i128 isn't enough. The magnitude is kept in base 2^32
+// digits from the lowest one and never ends with a zero digit, so zero has no digits and equal
+// numbers always look the same.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    digits: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> Self {
+        Self { negative: false, digits: self.digits.clone() }
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.digits.len() > 4 {
+            return None;
+        }
+        let magnitude = self.digits.iter().rev().fold(0u128, |result, &digit| result << 32 | digit as u128);
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    fn from_parts(negative: bool, mut digits: Vec<u32>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        Self { negative: negative && !digits.is_empty(), digits }
+    }
+
+    // The quotient and remainder rounded toward zero like the primitive integers, the
+    // remainder has the sign of `self`. Panics when dividing by zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        assert!(!divisor.is_zero(), "attempt to divide by zero");
+        let negative = self.negative != divisor.negative;
+
+        if divisor.digits.len() == 1 {
+            let (quotient, remainder) = div_rem_digit(&self.digits, divisor.digits[0]);
+            return (Self::from_parts(negative, quotient), Self::from_parts(self.negative, vec![remainder]));
+        }
+
+        // Long division one bit at a time, good enough for the sizes the puzzles need
+        let mut quotient = vec![0u32; self.digits.len()];
+        let mut remainder: Vec<u32> = Vec::new();
+        for bit in (0..self.digits.len() * 32).rev() {
+            shift_left_one(&mut remainder);
+            if self.digits[bit / 32] >> (bit % 32) & 1 == 1 {
+                if remainder.is_empty() {
+                    remainder.push(1);
+                } else {
+                    remainder[0] |= 1;
+                }
+            }
+            if cmp_magnitude(&remainder, &divisor.digits) != Ordering::Less {
+                remainder = sub_magnitude(&remainder, &divisor.digits);
+                quotient[bit / 32] |= 1 << (bit % 32);
+            }
+        }
+        (Self::from_parts(negative, quotient), Self::from_parts(self.negative, remainder))
+    }
+
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let mut a = self.abs();
+        let mut b = other.abs();
+        while !b.is_zero() {
+            let (_, remainder) = a.div_rem(&b);
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+// `a` has to be at least as big as `b`
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &digit) in a.iter().enumerate() {
+        let mut difference = digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if difference < 0 {
+            difference += 1 << 32;
+            borrow = 1;
+        }
+        result.push(difference as u32);
+    }
+    while result.last() == Some(&0) {
+        result.pop();
+    }
+    result
+}
+
+fn div_rem_digit(digits: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; digits.len()];
+    let mut remainder = 0u64;
+    for i in (0..digits.len()).rev() {
+        let current = remainder << 32 | digits[i] as u64;
+        quotient[i] = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    (quotient, remainder as u32)
+}
+
+fn shift_left_one(digits: &mut Vec<u32>) {
+    let mut carry = 0;
+    for digit in digits.iter_mut() {
+        let next_carry = *digit >> 31;
+        *digit = *digit << 1 | carry;
+        carry = next_carry;
+    }
+    if carry > 0 {
+        digits.push(carry);
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut digits = Vec::new();
+        while magnitude > 0 {
+            digits.push(magnitude as u32);
+            magnitude >>= 32;
+        }
+        Self::from_parts(value < 0, digits)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        Self::from(value as i128)
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(value: i32) -> Self {
+        Self::from(value as i128)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.digits, &other.digits),
+            (true, true) => cmp_magnitude(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.digits.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.digits)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.digits, &other.digits));
+        }
+        // The signs differ so the bigger magnitude decides the sign
+        match cmp_magnitude(&self.digits, &other.digits) {
+            Ordering::Less => BigInt::from_parts(other.negative, sub_magnitude(&other.digits, &self.digits)),
+            _ => BigInt::from_parts(self.negative, sub_magnitude(&self.digits, &other.digits)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &(-other)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        if self.is_zero() || other.is_zero() {
+            return BigInt::zero();
+        }
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.digits.iter().enumerate() {
+                let current = digits[i + j] as u64 + a as u64 * b as u64 + carry;
+                digits[i + j] = current as u32;
+                carry = current >> 32;
+            }
+            digits[i + other.digits.len()] = carry as u32;
+        }
+        BigInt::from_parts(self.negative != other.negative, digits)
+    }
+}
+
+// The owned versions so sums like `a + b` read the same as with the primitive integers
+impl Add for BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: BigInt) -> BigInt {
+        &self + &other
+    }
+}
+
+impl Sub for BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: BigInt) -> BigInt {
+        &self - &other
+    }
+}
+
+impl Mul for BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: BigInt) -> BigInt {
+        &self * &other
+    }
+}
+
+impl std::iter::Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::zero(), |result, value| &result + &value)
+    }
+}
+
+// 10^9 is the biggest power of ten in a digit, so the decimal text is handled 9 digits at a time
+const DECIMAL_BASE: u32 = 1_000_000_000;
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let (negative, decimal) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if decimal.is_empty() || !decimal.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(format!("Invalid integer `{text}`"));
+        }
+
+        let mut result = BigInt::zero();
+        let mut position = 0;
+        let mut chunk_length = match decimal.len() % 9 { 0 => 9, length => length };
+        while position < decimal.len() {
+            let chunk = &decimal[position..position + chunk_length];
+            let value: i64 = chunk.parse().map_err(|_| format!("Invalid integer `{text}`"))?;
+            result = &(&result * &BigInt::from(10i64.pow(chunk_length as u32))) + &BigInt::from(value);
+            position += chunk_length;
+            chunk_length = 9;
+        }
+        Ok(if negative { -result } else { result })
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut chunks = Vec::new();
+        let mut digits = self.digits.clone();
+        while !digits.is_empty() {
+            let (quotient, remainder) = div_rem_digit(&digits, DECIMAL_BASE);
+            chunks.push(remainder);
+            digits = quotient;
+            while digits.last() == Some(&0) {
+                digits.pop();
+            }
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks[chunks.len() - 1])?;
+        for chunk in chunks.iter().rev().skip(1) {
+            write!(f, "{chunk:09}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{check, Rng};
+
+    fn random_i128(rng: &mut Rng) -> i128 {
+        // Mostly small numbers so the operations don't overflow i128, sometimes big ones
+        let bits = rng.below(100) as u32 + 1;
+        let magnitude = (rng.next_u64() as u128) << 64 | rng.next_u64() as u128;
+        let value = (magnitude >> (128 - bits)) as i128;
+        if rng.chance(1, 2) { -value } else { value }
+    }
+
+    #[test]
+    fn matches_i128() {
+        check(
+            |rng| (random_i128(rng), random_i128(rng) >> rng.below(60)),
+            |&(a, b)| {
+                let smaller = |value: i128| [0, value / 2, value - value.signum()].into_iter().filter(move |&smaller| smaller != value);
+                smaller(a).map(|a| (a, b)).chain(smaller(b).map(|b| (a, b))).collect()
+            },
+            |&(a, b)| {
+                let (big_a, big_b) = (BigInt::from(a), BigInt::from(b));
+                let division_matches = b == 0
+                    || big_a.div_rem(&big_b) == (BigInt::from(a / b), BigInt::from(a % b));
+                (&big_a + &big_b).to_i128() == Some(a + b)
+                    && (&big_a - &big_b).to_i128() == Some(a - b)
+                    && (&big_a * &big_b).to_i128() == a.checked_mul(b)
+                    && big_a.cmp(&big_b) == a.cmp(&b)
+                    && big_a.to_string() == a.to_string()
+                    && a.to_string().parse::<BigInt>() == Ok(big_a.clone())
+                    && division_matches
+            },
+        );
+    }
+
+    #[test]
+    fn bigger_than_i128() {
+        let big: BigInt = "-340282366920938463463374607431768211456123456789".parse().unwrap();
+        assert_eq!(big.to_i128(), None);
+        assert_eq!(big.to_string(), "-340282366920938463463374607431768211456123456789");
+
+        let square = &big * &big;
+        let (quotient, remainder) = square.div_rem(&big);
+        assert_eq!(quotient, big);
+        assert!(remainder.is_zero());
+        assert_eq!((&square + &BigInt::from(7)).div_rem(&big).1, BigInt::from(7));
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!(BigInt::from(12).gcd(&BigInt::from(-18)), BigInt::from(6));
+    }
+}
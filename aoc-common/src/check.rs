@@ -0,0 +1,112 @@
+use std::env;
+use std::fmt::Debug;
+
+use crate::Rng;
+
+// Smaller versions of a number, the smallest first
+pub fn shrink_u64(value: u64) -> Vec<u64> {
+    let mut result = Vec::new();
+    for candidate in [0, value / 2, value.saturating_sub(1)] {
+        if candidate < value && !result.contains(&candidate) {
+            result.push(candidate);
+        }
+    }
+    return result;
+}
+
+// Smaller versions of a number, closer to 0
+pub fn shrink_i64(value: i64) -> Vec<i64> {
+    return shrink_u64(value.unsigned_abs()).into_iter()
+        .map(|candidate| if value < 0 { -(candidate as i64) } else { candidate as i64 })
+        .collect();
+}
+
+// Shorter versions of a list first (without its first or second half, then without each
+// element), then the list with one element shrunk
+pub fn shrink_vec<T: Clone>(items: &[T], shrink_item: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut result: Vec<Vec<T>> = Vec::new();
+    if items.len() > 1 {
+        result.push(items[items.len() / 2..].to_vec());
+        result.push(items[..items.len() / 2].to_vec());
+    }
+    for i in 0..items.len() {
+        let mut shorter = items.to_vec();
+        shorter.remove(i);
+        result.push(shorter);
+    }
+    for (i, item) in items.iter().enumerate() {
+        for smaller in shrink_item(item) {
+            let mut shrunk = items.to_vec();
+            shrunk[i] = smaller;
+            result.push(shrunk);
+        }
+    }
+    return result;
+}
+
+// Tries `cases` random values and returns the first one the property fails on, shrunk for as
+// long as a smaller version of it still fails
+pub fn find_counterexample<T: Clone>(
+    rng: &mut Rng,
+    cases: usize,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> bool,
+) -> Option<T> {
+    for _ in 0..cases {
+        let mut value = generate(rng);
+        if property(&value) {
+            continue;
+        }
+
+        'shrinking: loop {
+            for smaller in shrink(&value) {
+                if !property(&smaller) {
+                    value = smaller;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+        return Some(value);
+    }
+    return None;
+}
+
+// The property tests of the days. The seed and amount of cases can be changed with the
+// CHECK_SEED and CHECK_CASES environment variables, the seed is printed when a property fails
+// so it can be run again.
+pub fn check<T: Clone + Debug>(
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> bool,
+) {
+    let seed = env::var("CHECK_SEED").ok().and_then(|seed| seed.parse().ok()).unwrap_or(2023);
+    let cases = env::var("CHECK_CASES").ok().and_then(|cases| cases.parse().ok()).unwrap_or(200);
+
+    let mut rng = Rng::new(seed);
+    if let Some(value) = find_counterexample(&mut rng, cases, generate, shrink, property) {
+        panic!("Property failed with CHECK_SEED={seed} on {value:#?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_to_a_minimal_counterexample() {
+        let mut rng = Rng::new(7);
+        let counterexample = find_counterexample(
+            &mut rng,
+            100,
+            |rng| (0..rng.below(20)).map(|_| rng.below(100)).collect::<Vec<u64>>(),
+            |items| shrink_vec(items, |&item| shrink_u64(item)),
+            |items| items.iter().sum::<u64>() < 100,
+        );
+        // Nothing can be removed or made smaller without the sum dropping under 100
+        let counterexample = counterexample.unwrap();
+        assert_eq!(counterexample.iter().sum::<u64>(), 100);
+        assert!(!counterexample.contains(&0));
+    }
+}
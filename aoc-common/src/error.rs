@@ -0,0 +1,60 @@
+use std::fmt;
+use std::io;
+use std::ops::Range;
+
+#[derive(Debug)]
+pub enum Error {
+    // Reading a file failed
+    Io { path: String, source: io::Error },
+    // The input doesn't follow the puzzle format. `line` and `column` are 1-based and the
+    // column counts bytes, `token` is the text that couldn't be parsed.
+    Parse { line: usize, column: usize, token: String, message: String },
+    // The input could be read but breaks a rule of the puzzle, or an option is invalid
+    Validation(String),
+    // The input is fine but there's no answer for it
+    NoSolution(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse(message: impl Into<String>, line: usize, column: usize, token: &str) -> Self {
+        Self::Parse { line, column, token: token.to_string(), message: message.into() }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::Validation(message.into())
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        Self::NoSolution(message.into())
+    }
+
+    // The 0-based byte range of the token inside its line, only for parse errors
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Self::Parse { column, token, .. } => Some(column - 1..column - 1 + token.len()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "Failed to read {path}: {source}"),
+            Self::Parse { line, column, token, message } => write!(f, "line {line}, column {column}: {message} `{token}`"),
+            Self::Validation(message) => write!(f, "{message}"),
+            Self::NoSolution(message) => write!(f, "No solution: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
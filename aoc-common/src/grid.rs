@@ -0,0 +1,62 @@
+use crate::{Error, Result};
+
+// A rectangular grid stored row by row. Rows and columns are i32 so neighbours of the
+// edges can be computed without underflowing, `contains` tells whether they're inside.
+#[derive(Debug, Clone)]
+pub struct Grid<T> {
+    pub data: Vec<T>,
+    pub rows: i32,
+    pub cols: i32,
+}
+
+impl<T> Grid<T> {
+    pub fn new() -> Self {
+        Self { data: Vec::new(), rows: 0, cols: 0 }
+    }
+
+    // Appends a whole row, every row has to be as long as the first one
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) -> Result<()> {
+        let before = self.data.len();
+        self.data.extend(row);
+        let cols = (self.data.len() - before) as i32;
+        if self.rows == 0 {
+            self.cols = cols;
+        } else if self.cols != cols {
+            self.data.truncate(before);
+            return Err(Error::validation(format!("Invalid amount of columns, row {} has {cols} but the first row has {}", self.rows + 1, self.cols)));
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    pub fn index_of(&self, row: i32, col: i32) -> usize {
+        return (row * self.cols + col) as usize;
+    }
+
+    pub fn contains(&self, row: i32, col: i32) -> bool {
+        return (0 <= row && row < self.rows) && (0 <= col && col < self.cols);
+    }
+
+    pub fn get(&self, row: i32, col: i32) -> Option<&T> {
+        if self.contains(row, col) {
+            Some(&self.data[self.index_of(row, col)])
+        } else {
+            None
+        }
+    }
+
+    // The up to 8 cells around (row, col) that are inside the grid
+    pub fn neighbours(&self, row: i32, col: i32) -> impl Iterator<Item = (i32, i32)> + '_ {
+        (-1..=1)
+            .flat_map(|drow| (-1..=1).map(move |dcol| (drow, dcol)))
+            .filter(|&(drow, dcol)| drow != 0 || dcol != 0)
+            .map(move |(drow, dcol)| (row + drow, col + dcol))
+            .filter(|&(row, col)| self.contains(row, col))
+    }
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,30 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+
+use crate::{Error, Result};
+
+// The input file path is always the first argument of every day, `-` is stdin
+pub fn input_path(args: &[String]) -> Result<&str> {
+    args.get(1)
+        .map(|path| path.as_str())
+        .ok_or_else(|| Error::validation("Please provide an input file path, or - for stdin"))
+}
+
+pub fn read_input(file_path: &str) -> Result<String> {
+    let io_error = |source| Error::Io { path: file_path.to_string(), source };
+    if file_path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map_err(io_error)?;
+        return Ok(input);
+    }
+    fs::read_to_string(file_path).map_err(io_error)
+}
+
+// The input as a reader for the days that go through it line by line without keeping it all
+pub fn open_input(file_path: &str) -> Result<Box<dyn BufRead>> {
+    if file_path == "-" {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(file_path).map_err(|source| Error::Io { path: file_path.to_string(), source })?;
+    Ok(Box::new(BufReader::new(file)))
+}
@@ -0,0 +1,21 @@
+// Things every day needs: reading the input, reporting errors, parsing numbers and grids,
+// the `Solution` trait each day implements, the seeded random numbers of `aoc gen` and the
+// property tests that use them.
+
+mod bigint;
+mod check;
+mod error;
+mod grid;
+mod input;
+mod parse;
+mod rng;
+mod solution;
+
+pub use bigint::BigInt;
+pub use check::{check, find_counterexample, shrink_i64, shrink_u64, shrink_vec};
+pub use error::{Error, Result};
+pub use grid::Grid;
+pub use input::{input_path, open_input, read_input};
+pub use parse::{lines, read_lines, Line};
+pub use rng::Rng;
+pub use solution::{solve, Solution};
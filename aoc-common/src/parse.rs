@@ -0,0 +1,82 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+// One line of the input with its 1-based line number, so anything that fails to parse in it
+// can be reported with its position
+#[derive(Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    // The 1-based byte column of `token`, which has to be a slice of this line's text.
+    // Anything else is reported at column 1.
+    pub fn column_of(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+        if start <= token_start && token_start + token.len() <= start + self.text.len() {
+            token_start - start + 1
+        } else {
+            1
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>, token: &str) -> Error {
+        Error::parse(message, self.number, self.column_of(token), token)
+    }
+
+    pub fn parse<T: FromStr>(&self, token: &str) -> Result<T> {
+        token.parse().map_err(|_| self.error("Failed to parse number", token))
+    }
+
+    // Parses every whitespace separated number in `text`, a slice of this line
+    pub fn numbers<T: FromStr>(&self, text: &str) -> Result<Vec<T>> {
+        let mut result = Vec::new();
+        for num in text.split_whitespace() {
+            result.push(self.parse(num)?);
+        }
+        Ok(result)
+    }
+}
+
+// Every line of the input with its line number
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line { number: i + 1, text })
+}
+
+// The same for a reader, one line at a time so only the current line is in memory. `path` is
+// only for the error when reading fails.
+pub fn read_lines(mut reader: impl BufRead, path: &str, mut f: impl FnMut(Line) -> Result<()>) -> Result<()> {
+    let mut buffer = String::new();
+    let mut number = 0;
+    loop {
+        buffer.clear();
+        let read = reader.read_line(&mut buffer).map_err(|source| Error::Io { path: path.to_string(), source })?;
+        if read == 0 {
+            return Ok(());
+        }
+        number += 1;
+        let text = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        f(Line { number, text: text.strip_suffix('\r').unwrap_or(text) })?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_lines_matches_lines() {
+        let input = "first\r\nsecond\n\nlast";
+        let mut read: Vec<(usize, String)> = Vec::new();
+        read_lines(input.as_bytes(), "test", |line| {
+            read.push((line.number, line.text.to_string()));
+            Ok(())
+        }).unwrap();
+        let expected: Vec<(usize, String)> = lines(input).map(|line| (line.number, line.text.to_string())).collect();
+        assert_eq!(read, expected);
+    }
+}
@@ -0,0 +1,73 @@
+use std::ops::Range;
+
+// A small seeded random generator (splitmix64), so generated inputs can be reproduced from the seed
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        return z ^ (z >> 31);
+    }
+
+    // A number in 0..n, n has to be more than 0
+    pub fn below(&mut self, n: u64) -> u64 {
+        return ((self.next_u64() as u128 * n as u128) >> 64) as u64;
+    }
+
+    // A number in the range, which can't be empty. Wraps so ranges wider than i64::MAX work too.
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "empty range {range:?}");
+        return range.start.wrapping_add(self.below(range.end.abs_diff(range.start)) as i64);
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        return self.below(denominator) < numerator;
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        return &items[self.below(items.len() as u64) as usize];
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_stays_inside() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!((-5..5).contains(&rng.range(-5..5)));
+            assert!((i64::MIN..i64::MAX).contains(&rng.range(i64::MIN..i64::MAX)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn empty_range() {
+        Rng::new(1).range(5..5);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn reversed_range() {
+        Rng::new(1).range(Range { start: 5, end: -5 });
+    }
+}
@@ -0,0 +1,19 @@
+use std::fmt::Display;
+
+use crate::Result;
+
+// A day's puzzle. `parse` turns the input file content into the puzzle, and both parts are
+// answered from it.
+pub trait Solution: Sized {
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self>;
+    fn part1(&self) -> Result<Self::Part1>;
+    fn part2(&self) -> Result<Self::Part2>;
+}
+
+pub fn solve<S: Solution>(input: &str) -> Result<(S::Part1, S::Part2)> {
+    let puzzle = S::parse(input)?;
+    Ok((puzzle.part1()?, puzzle.part2()?))
+}
@@ -0,0 +1,224 @@
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use aoc_common::{read_input, Error, Result};
+
+use crate::days;
+use crate::json::Json;
+
+// Every run after the first is skipped once an input took this long in total, so the slow days
+// don't take forever
+const TIME_BUDGET: Duration = Duration::from_secs(1);
+
+// Differences below this are just noise from the machine, not a regression
+const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+// The fastest time of parsing and of each part for one input of a day. A part that failed on
+// the input (the day 8 examples only work for one part each) has no time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub day: u32,
+    pub input: String,
+    pub parse: Duration,
+    pub parts: Vec<(u8, Option<Duration>)>,
+}
+
+impl BenchResult {
+    // Everything that was timed, by the name it has in the JSON file
+    fn stages(&self) -> Vec<(String, Duration)> {
+        let mut result = vec![("parse".to_string(), self.parse)];
+        for (part, time) in &self.parts {
+            if let Some(time) = time {
+                result.push((format!("part{part}"), *time));
+            }
+        }
+        result
+    }
+}
+
+// The examples first and then the real input, whichever of them exists
+pub fn inputs_of(day: u32) -> Vec<(String, String)> {
+    let mut names: Vec<String> = Vec::new();
+    let directory = days::day_file(day, "");
+    if let Ok(entries) = fs::read_dir(&directory) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("example") && name.ends_with(".txt") {
+                names.push(name.trim_end_matches(".txt").to_string());
+            }
+        }
+    }
+    names.sort();
+    names.push("input".to_string());
+
+    names.into_iter()
+        .map(|name| { let path = days::day_file(day, &format!("{name}.txt")); (name, path) })
+        .filter(|(_, path)| Path::new(path).exists())
+        .collect()
+}
+
+pub fn bench_input(day: u32, name: &str, input: &str, runs: usize) -> Result<BenchResult> {
+    let solver = days::find(day).ok_or_else(|| Error::validation(format!("Day {day} isn't solved")))?;
+    let mut result = BenchResult { day, input: name.to_string(), parse: Duration::MAX, parts: Vec::new() };
+
+    let start = Instant::now();
+    for run in 0..runs.max(1) {
+        if run > 0 && start.elapsed() > TIME_BUDGET {
+            break;
+        }
+        let report = solver(input, None)?;
+        result.parse = result.parse.min(report.parse_time);
+        if result.parts.is_empty() {
+            result.parts = report.parts.iter().map(|part| (part.part, None)).collect();
+        }
+        for (part, (_, time)) in report.parts.iter().zip(result.parts.iter_mut()) {
+            if part.answer.is_ok() {
+                *time = Some(time.map_or(part.time, |time| time.min(part.time)));
+            }
+        }
+    }
+    Ok(result)
+}
+
+pub fn bench_day(day: u32, runs: usize) -> Result<Vec<BenchResult>> {
+    let mut results = Vec::new();
+    for (name, path) in inputs_of(day) {
+        results.push(bench_input(day, &name, &read_input(&path)?, runs)?);
+    }
+    Ok(results)
+}
+
+// Times are written in microseconds
+pub fn to_json(results: &[BenchResult]) -> Json {
+    let entries = results.iter().map(|result| {
+        let mut entry = vec![
+            ("day".to_string(), Json::Number(result.day as f64)),
+            ("input".to_string(), Json::String(result.input.clone())),
+        ];
+        for (stage, time) in result.stages() {
+            entry.push((stage, Json::Number(time.as_nanos() as f64 / 1e3)));
+        }
+        Json::Object(entry)
+    });
+    Json::Object(vec![("results".to_string(), Json::Array(entries.collect()))])
+}
+
+pub fn from_json(json: &Json) -> Result<Vec<BenchResult>> {
+    let invalid = |message: &str| Error::validation(format!("Invalid benchmark file: {message}"));
+    let micros = |value: &Json| value.as_f64()
+        .filter(|time| *time >= 0.0)
+        .map(|time| Duration::from_nanos((time * 1e3).round() as u64));
+
+    let entries = json.get("results").and_then(Json::as_array).ok_or_else(|| invalid("there are no results"))?;
+    let mut results = Vec::new();
+    for entry in entries {
+        let day = entry.get("day").and_then(Json::as_f64).ok_or_else(|| invalid("a result has no day"))? as u32;
+        let input = entry.get("input").and_then(Json::as_str).ok_or_else(|| invalid("a result has no input"))?;
+        let parse = entry.get("parse").and_then(micros).ok_or_else(|| invalid("a result has no parse time"))?;
+        let parts = [1, 2].iter()
+            .map(|&part| (part, entry.get(&format!("part{part}")).and_then(micros)))
+            .collect();
+        results.push(BenchResult { day, input: input.to_string(), parse, parts });
+    }
+    Ok(results)
+}
+
+pub fn load(path: &str) -> Result<Vec<BenchResult>> {
+    from_json(&Json::parse(&read_input(path)?)?)
+}
+
+pub fn save(path: &str, results: &[BenchResult]) -> Result<()> {
+    fs::write(path, format!("{}\n", to_json(results)))
+        .map_err(|source| Error::Io { path: path.to_string(), source })
+}
+
+// A day whose total time went past the threshold
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u32,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    pub fn percent(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+// Compares the total time of every day, only counting the stages timed in both runs so adding
+// an example or fixing a failing part doesn't look like a regression. `threshold` is in percent.
+pub fn regressions(baseline: &[BenchResult], current: &[BenchResult], threshold: f64) -> Vec<Regression> {
+    let mut days: Vec<u32> = current.iter().map(|result| result.day).collect();
+    days.dedup();
+
+    let mut result = Vec::new();
+    for day in days {
+        let mut baseline_total = Duration::ZERO;
+        let mut current_total = Duration::ZERO;
+        for now in current.iter().filter(|result| result.day == day) {
+            let Some(before) = baseline.iter().find(|before| before.day == day && before.input == now.input) else {
+                continue;
+            };
+            let before_stages = before.stages();
+            for (stage, time) in now.stages() {
+                if let Some((_, before_time)) = before_stages.iter().find(|(name, _)| *name == stage) {
+                    baseline_total += *before_time;
+                    current_total += time;
+                }
+            }
+        }
+
+        let limit = baseline_total.as_secs_f64() * (1.0 + threshold / 100.0);
+        if current_total.as_secs_f64() > limit && current_total - baseline_total > NOISE_FLOOR {
+            result.push(Regression { day, baseline: baseline_total, current: current_total });
+        }
+    }
+    result
+}
+
+// Replaces the results of the days in `current` and keeps the others
+pub fn merge(baseline: &[BenchResult], current: &[BenchResult]) -> Vec<BenchResult> {
+    let mut result: Vec<BenchResult> = baseline.iter()
+        .filter(|before| current.iter().all(|now| now.day != before.day))
+        .cloned()
+        .collect();
+    result.extend(current.iter().cloned());
+    result.sort_by_key(|result| result.day);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(day: u32, input: &str, millis: [u64; 3]) -> BenchResult {
+        BenchResult {
+            day,
+            input: input.to_string(),
+            parse: Duration::from_millis(millis[0]),
+            parts: vec![(1, Some(Duration::from_millis(millis[1]))), (2, Some(Duration::from_millis(millis[2])))],
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut results = vec![result(1, "example", [1, 2, 3]), result(8, "example2", [4, 5, 6])];
+        results[1].parts[0].1 = None;
+        assert_eq!(from_json(&Json::parse(&to_json(&results).to_string()).unwrap()).unwrap(), results);
+    }
+
+    #[test]
+    fn regression_threshold() {
+        let baseline = vec![result(1, "input", [10, 20, 30]), result(2, "input", [10, 20, 30])];
+        let current = vec![result(1, "input", [10, 20, 40]), result(2, "input", [10, 30, 40])];
+        // Day 1 is 16% slower and day 2 is 33% slower
+        assert_eq!(regressions(&baseline, &current, 20.0), vec![Regression {
+            day: 2,
+            baseline: Duration::from_millis(60),
+            current: Duration::from_millis(80),
+        }]);
+        assert!(regressions(&baseline, &current, 50.0).is_empty());
+    }
+}
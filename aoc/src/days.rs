@@ -0,0 +1,63 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use aoc_common::{Result, Solution};
+
+// The answer of one part and how long it took
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Result<String>,
+    pub time: Duration,
+}
+
+pub struct DayReport {
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+// Parses the input and answers the asked part, or both parts when `part` is None
+pub type Solver = fn(&str, Option<u8>) -> Result<DayReport>;
+
+fn solver<S: Solution>(input: &str, part: Option<u8>) -> Result<DayReport> {
+    let start = Instant::now();
+    let puzzle = S::parse(input)?;
+    let mut report = DayReport { parse_time: start.elapsed(), parts: Vec::new() };
+
+    if part.is_none() || part == Some(1) {
+        let start = Instant::now();
+        let answer = puzzle.part1().map(|answer| answer.to_string());
+        report.parts.push(PartReport { part: 1, answer, time: start.elapsed() });
+    }
+    if part.is_none() || part == Some(2) {
+        let start = Instant::now();
+        let answer = puzzle.part2().map(|answer| answer.to_string());
+        report.parts.push(PartReport { part: 2, answer, time: start.elapsed() });
+    }
+
+    Ok(report)
+}
+
+pub const DAYS: [(u32, Solver); 9] = [
+    (1, solver::<day1::Day1>),
+    (2, solver::<day2::Day2>),
+    (3, solver::<day3::Day3>),
+    (4, solver::<day4::Day4>),
+    (5, solver::<day5::Day5>),
+    (6, solver::<day6::Day6>),
+    (7, solver::<day7::Day7>),
+    (8, solver::<day8::Day8>),
+    (9, solver::<day9::Day9>),
+];
+
+pub fn find(day: u32) -> Option<Solver> {
+    DAYS.iter().find(|(number, _)| *number == day).map(|(_, solver)| *solver)
+}
+
+// A file of the day's directory from the current directory, or else from the workspace
+pub fn day_file(day: u32, name: &str) -> String {
+    let path = format!("day{day}/{name}");
+    if Path::new(&path).exists() {
+        return path;
+    }
+    format!("{}/../{path}", env!("CARGO_MANIFEST_DIR"))
+}
@@ -0,0 +1,91 @@
+use aoc_common::{Error, Result, Rng};
+
+// The options of `aoc gen`. Each day reads the ones that fit its puzzle and uses its own default
+// for the rest, see `generate`.
+#[derive(Debug, Default, Clone)]
+pub struct GenOptions {
+    pub seed: u64,
+    pub size: Option<usize>,
+    // Day 3, the size is used for both when one of them isn't given
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    // Day 3, percent of the cells that start a number
+    pub density: Option<u64>,
+    // Day 5, the most rules of a map
+    pub rules: Option<usize>,
+    // Day 8, how many starts there are and the most rounds of the instructions a start needs
+    // to reach its end node
+    pub starts: Option<usize>,
+    pub rounds: Option<usize>,
+}
+
+// A random input of `day` that its parser accepts, the same options always give the same input
+pub fn generate(day: u32, options: &GenOptions) -> Result<String> {
+    let mut rng = Rng::new(options.seed);
+    let size = |default: usize| options.size.unwrap_or(default);
+
+    let input = match day {
+        1 => day1::random_document(&mut rng, size(1000), &day1::Vocabulary::from_locale("en")?),
+        2 => day2::games_to_input(&day2::random_games(&mut rng, size(100), 6, 20)),
+        3 => {
+            let width = options.width.unwrap_or(size(140));
+            let height = options.height.unwrap_or(size(140));
+            let density = options.density.unwrap_or(12);
+            if density > 100 {
+                return Err(Error::validation(format!("The density is a percentage but got {density}")));
+            }
+            day3::random_schematic(&mut rng, width, height, density)
+        },
+        // With more matches the amount of copies grows exponentially and overflows part 2
+        4 => day4::cards_to_input(&day4::random_cards(&mut rng, size(200), 2)),
+        5 => day5::AlmanacNumbers::random(&mut rng, size(1 << 32).max(1) as i64, options.rules.unwrap_or(30)).to_input(),
+        6 => {
+            // Part 2 joins the digits of every race into one number, more races don't fit in it
+            if size(4) > 4 {
+                return Err(Error::validation("Day 6 has at most 4 races"));
+            }
+            day6::races_to_input(&day6::random_races(&mut rng, size(4), 100, true))
+        },
+        7 => day7::hands_to_input(&day7::random_hands(&mut rng, size(1000))),
+        8 => {
+            let instructions = size(50).max(1);
+            let starts = options.starts.unwrap_or(6).max(1);
+            let rounds = options.rounds.unwrap_or(5).max(1);
+            if starts > 676 || instructions * rounds * starts > 16000 {
+                return Err(Error::validation("A network has at most 676 starts and 16000 nodes, \
+                    which is the size times the rounds times the starts"));
+            }
+            day8::random_network(&mut rng, instructions, starts, rounds).0
+        },
+        9 => day9::sequences_to_input(&day9::random_sequences(&mut rng, size(200), 21, 5)),
+        _ => return Err(Error::validation(format!("Day {day} has no generator"))),
+    };
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn every_day_parses_and_solves_its_input() {
+        for (day, solver) in days::DAYS {
+            for seed in 0..5 {
+                let options = GenOptions { seed, ..GenOptions::default() };
+                let input = generate(day, &options).unwrap();
+                let report = solver(&input, None).unwrap_or_else(|e| panic!("Day {day} with seed {seed}: {e}\n{input}"));
+                for part in report.parts {
+                    assert!(part.answer.is_ok(), "Day {day} part {} with seed {seed} failed\n{input}", part.part);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        let options = GenOptions { seed: 42, size: Some(10), ..GenOptions::default() };
+        assert_eq!(generate(7, &options).unwrap(), generate(7, &options).unwrap());
+        assert_ne!(generate(7, &options).unwrap(), generate(7, &GenOptions { seed: 43, ..options }).unwrap());
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt::{self, Write};
+
+use aoc_common::{Error, Result};
+
+// Just enough JSON for the benchmark files, there are no dependencies in this workspace
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    // Keeps the order of the keys so the written files are stable
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Json> {
+        let mut parser = Parser { text, position: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.position < text.len() {
+            return Err(parser.error("Unexpected text after the JSON value"));
+        }
+        Ok(value)
+    }
+
+    fn write_indented(&self, out: &mut String, indent: usize) -> fmt::Result {
+        match self {
+            Json::Null => out.write_str("null"),
+            Json::Bool(value) => write!(out, "{value}"),
+            Json::Number(number) => write!(out, "{number}"),
+            Json::String(text) => write_string(out, text),
+            Json::Array(items) if items.is_empty() => out.write_str("[]"),
+            Json::Object(entries) if entries.is_empty() => out.write_str("{}"),
+            Json::Array(items) => {
+                out.write_str("[\n")?;
+                for (i, item) in items.iter().enumerate() {
+                    write!(out, "{:width$}", "", width = indent + 2)?;
+                    item.write_indented(out, indent + 2)?;
+                    out.write_str(if i + 1 < items.len() { ",\n" } else { "\n" })?;
+                }
+                write!(out, "{:indent$}]", "")
+            },
+            Json::Object(entries) => {
+                out.write_str("{\n")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    write!(out, "{:width$}", "", width = indent + 2)?;
+                    write_string(out, key)?;
+                    out.write_str(": ")?;
+                    value.write_indented(out, indent + 2)?;
+                    out.write_str(if i + 1 < entries.len() { ",\n" } else { "\n" })?;
+                }
+                write!(out, "{:indent$}}}", "")
+            },
+        }
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out = String::new();
+        self.write_indented(&mut out, 0)?;
+        f.write_str(&out)
+    }
+}
+
+fn write_string(out: &mut String, text: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for ch in text.chars() {
+        match ch {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\t' => out.write_str("\\t")?,
+            ch if (ch as u32) < 0x20 => write!(out, "\\u{:04x}", ch as u32)?,
+            ch => out.write_char(ch)?,
+        }
+    }
+    out.write_char('"')
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    // Errors point at the line and column of where the parser stopped
+    fn error(&self, message: &str) -> Error {
+        let before = &self.text[..self.position];
+        let line = before.matches('\n').count() + 1;
+        let column = self.position - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        let token: String = self.text[self.position..].chars().take_while(|ch| !ch.is_whitespace()).take(16).collect();
+        Error::parse(message, line, column, &token)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(ch) = self.peek().filter(|ch| ch.is_whitespace()) {
+            self.position += ch.len_utf8();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        self.skip_whitespace();
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("Expecting `{expected}`")));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Json> {
+        self.skip_whitespace();
+        let rest = &self.text[self.position..];
+        for (word, value) in [("null", Json::Null), ("true", Json::Bool(true)), ("false", Json::Bool(false))] {
+            if rest.starts_with(word) {
+                self.position += word.len();
+                return Ok(value);
+            }
+        }
+
+        match self.peek() {
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('[') => {
+                self.position += 1;
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(']') {
+                    self.position += 1;
+                    return Ok(Json::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(',') => self.position += 1,
+                        Some(']') => { self.position += 1; return Ok(Json::Array(items)); },
+                        _ => return Err(self.error("Expecting `,` or `]`")),
+                    }
+                }
+            },
+            Some('{') => {
+                self.position += 1;
+                let mut entries = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some('}') {
+                    self.position += 1;
+                    return Ok(Json::Object(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.string()?;
+                    self.expect(':')?;
+                    entries.push((key, self.value()?));
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(',') => self.position += 1,
+                        Some('}') => { self.position += 1; return Ok(Json::Object(entries)); },
+                        _ => return Err(self.error("Expecting `,` or `}`")),
+                    }
+                }
+            },
+            Some(ch) if ch == '-' || ch.is_ascii_digit() => {
+                let length = rest.find(|ch: char| !(ch.is_ascii_digit() || "+-.eE".contains(ch))).unwrap_or(rest.len());
+                let number = rest[..length].parse().map_err(|_| self.error("Failed to parse number"))?;
+                self.position += length;
+                Ok(Json::Number(number))
+            },
+            _ => Err(self.error("Expecting a JSON value")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        if self.peek() != Some('"') {
+            return Err(self.error("Expecting a string"));
+        }
+        self.position += 1;
+
+        let mut result = String::new();
+        loop {
+            let ch = self.peek().ok_or_else(|| self.error("Unterminated string"))?;
+            self.position += ch.len_utf8();
+            match ch {
+                '"' => return Ok(result),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("Unterminated string"))?;
+                    self.position += escaped.len_utf8();
+                    match escaped {
+                        '"' | '\\' | '/' => result.push(escaped),
+                        'n' => result.push('\n'),
+                        't' => result.push('\t'),
+                        'r' => result.push('\r'),
+                        'u' => {
+                            let code = self.text.get(self.position..self.position + 4)
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("Invalid unicode escape"))?;
+                            result.push(code);
+                            self.position += 4;
+                        },
+                        _ => return Err(self.error("Invalid escape")),
+                    }
+                },
+                ch => result.push(ch),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let value = Json::Object(vec![
+            ("name".to_string(), Json::String("day \"5\"\n".to_string())),
+            ("times".to_string(), Json::Array(vec![Json::Number(1.5), Json::Number(-20.0), Json::Null])),
+            ("empty".to_string(), Json::Object(Vec::new())),
+            ("ok".to_string(), Json::Bool(true)),
+        ]);
+        assert_eq!(Json::parse(&value.to_string()).unwrap(), value);
+    }
+
+    #[test]
+    fn error_position() {
+        let error = Json::parse("{\n  \"a\": 1,\n  \"b\" 2\n}").err().unwrap();
+        assert_eq!(error.to_string(), "line 3, column 7: Expecting `:` `2`");
+    }
+
+    #[test]
+    fn invalid_escape() {
+        let error = Json::parse("[\"\\é\"]").err().unwrap();
+        assert_eq!(error.to_string(), "line 1, column 6: Invalid escape `\"]`");
+    }
+}
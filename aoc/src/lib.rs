@@ -0,0 +1,7 @@
+// The runner's table of days, shared by the `aoc` binary and the answer tests
+pub mod days;
+// Timing every day and comparing it with a stored baseline, for `aoc bench`
+pub mod bench;
+pub mod json;
+// Random inputs of every day, for `aoc gen`
+pub mod gen;
@@ -0,0 +1,244 @@
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc::gen::{self, GenOptions};
+use aoc::{bench, days};
+use aoc_common::{read_input, Error, Result};
+
+const USAGE: &str = "Usage: aoc run <day|all> [--part 1|2] [--input <file>]
+       aoc bench [<day|all>] [--runs <n>] [--output <file>] [--baseline <file>] [--threshold <percent>] [--update-baseline]
+       aoc gen <day> [--seed <n>] [--size <n>] [--width <n>] [--height <n>] [--density <percent>] [--rules <n>] [--starts <n>] [--rounds <n>]";
+
+struct RunOptions {
+    // None means every day
+    day: Option<u32>,
+    part: Option<u8>,
+    input: Option<String>,
+}
+
+fn parse_run_options(args: &[String]) -> Result<RunOptions> {
+    let day = match args.first().map(|arg| arg.as_str()) {
+        Some("all") => None,
+        Some(day) => {
+            let day: u32 = day.parse().map_err(|_| Error::validation(format!("Invalid day `{day}`")))?;
+            days::find(day).ok_or_else(|| Error::validation(format!("Day {day} isn't solved")))?;
+            Some(day)
+        },
+        None => return Err(Error::validation(USAGE)),
+    };
+
+    let mut result = RunOptions { day, part: None, input: None };
+    let mut options = args[1..].iter();
+    while let Some(option) = options.next() {
+        let value = options.next().ok_or_else(|| Error::validation(format!("Missing value for {option}")))?;
+        match option.as_str() {
+            "--part" => match value.as_str() {
+                "1" => result.part = Some(1),
+                "2" => result.part = Some(2),
+                _ => return Err(Error::validation(format!("Invalid part `{value}`, it's either 1 or 2"))),
+            },
+            "--input" => result.input = Some(value.clone()),
+            _ => return Err(Error::validation(format!("Unknown option {option}"))),
+        }
+    }
+
+    if result.day.is_none() && result.input.is_some() {
+        return Err(Error::validation("--input can't be used with `aoc run all`"));
+    }
+    Ok(result)
+}
+
+fn default_input_path(day: u32) -> String {
+    days::day_file(day, "input.txt")
+}
+
+// Prints the answers of one day, returns whether everything was answered
+fn run_day(day: u32, part: Option<u8>, input_path: &str) -> bool {
+    println!("Day {day}");
+    let solver = days::find(day).expect("the day is checked when parsing the options");
+    let report = match read_input(input_path).and_then(|input| solver(&input, part)) {
+        Ok(report) => report,
+        Err(error_message) => {
+            println!("  ERROR: {error_message}");
+            return false;
+        },
+    };
+
+    let mut ok = true;
+    println!("  Parsing: {:?}", report.parse_time);
+    for part in report.parts {
+        match part.answer {
+            Ok(answer) => println!("  Part {}: {answer} ({:?})", part.part, part.time),
+            Err(error_message) => {
+                println!("  Part {}: ERROR: {error_message}", part.part);
+                ok = false;
+            },
+        }
+    }
+    ok
+}
+
+fn run(args: &[String]) -> Result<bool> {
+    let options = parse_run_options(args)?;
+    match options.day {
+        Some(day) => {
+            let input_path = options.input.unwrap_or_else(|| default_input_path(day));
+            Ok(run_day(day, options.part, &input_path))
+        },
+        None => {
+            let mut ok = true;
+            let mut total = Duration::ZERO;
+            for (day, _) in days::DAYS {
+                let start = Instant::now();
+                ok &= run_day(day, options.part, &default_input_path(day));
+                total += start.elapsed();
+            }
+            println!("Total: {total:?}");
+            Ok(ok)
+        },
+    }
+}
+
+struct BenchOptions {
+    // None means every day
+    day: Option<u32>,
+    runs: usize,
+    output: String,
+    baseline: String,
+    // In percent of the baseline
+    threshold: f64,
+    update_baseline: bool,
+}
+
+fn parse_bench_options(args: &[String]) -> Result<BenchOptions> {
+    let mut result = BenchOptions {
+        day: None,
+        runs: 5,
+        output: "bench.json".to_string(),
+        baseline: "bench_baseline.json".to_string(),
+        threshold: 20.0,
+        update_baseline: false,
+    };
+
+    let mut options = args.iter().peekable();
+    match options.peek().map(|arg| arg.as_str()) {
+        Some("all") => { options.next(); },
+        Some(day) if !day.starts_with("--") => {
+            let day: u32 = day.parse().map_err(|_| Error::validation(format!("Invalid day `{day}`")))?;
+            days::find(day).ok_or_else(|| Error::validation(format!("Day {day} isn't solved")))?;
+            result.day = Some(day);
+            options.next();
+        },
+        _ => (),
+    }
+
+    while let Some(option) = options.next() {
+        if option == "--update-baseline" {
+            result.update_baseline = true;
+            continue;
+        }
+        let value = options.next().ok_or_else(|| Error::validation(format!("Missing value for {option}")))?;
+        match option.as_str() {
+            "--runs" => result.runs = value.parse().ok().filter(|runs| *runs > 0)
+                .ok_or_else(|| Error::validation(format!("Invalid amount of runs `{value}`")))?,
+            "--output" => result.output = value.clone(),
+            "--baseline" => result.baseline = value.clone(),
+            "--threshold" => result.threshold = value.parse().ok().filter(|threshold: &f64| *threshold >= 0.0)
+                .ok_or_else(|| Error::validation(format!("Invalid threshold `{value}`, it's a percentage")))?,
+            _ => return Err(Error::validation(format!("Unknown option {option}"))),
+        }
+    }
+    Ok(result)
+}
+
+// Times every input of the asked days, writes the results and compares them with the baseline.
+// Returns false when a day got slower than the threshold allows.
+fn run_bench(args: &[String]) -> Result<bool> {
+    let options = parse_bench_options(args)?;
+    let selected: Vec<u32> = match options.day {
+        Some(day) => vec![day],
+        None => days::DAYS.iter().map(|(day, _)| *day).collect(),
+    };
+
+    let mut results = Vec::new();
+    for day in selected {
+        for result in bench::bench_day(day, options.runs)? {
+            let parts: Vec<String> = result.parts.iter()
+                .map(|(part, time)| match time {
+                    Some(time) => format!("part {part} {time:?}"),
+                    None => format!("part {part} failed"),
+                })
+                .collect();
+            println!("Day {day} {}: parse {:?}, {}", result.input, result.parse, parts.join(", "));
+            results.push(result);
+        }
+    }
+    bench::save(&options.output, &results)?;
+    println!("Wrote {}", options.output);
+
+    if !Path::new(&options.baseline).exists() {
+        bench::save(&options.baseline, &results)?;
+        println!("There was no baseline, saved these results as {}", options.baseline);
+        return Ok(true);
+    }
+
+    let baseline = bench::load(&options.baseline)?;
+    let regressions = bench::regressions(&baseline, &results, options.threshold);
+    for regression in &regressions {
+        println!("Day {} regressed by {:.1}%: {:?} -> {:?}",
+            regression.day, regression.percent(), regression.baseline, regression.current);
+    }
+    if options.update_baseline {
+        bench::save(&options.baseline, &bench::merge(&baseline, &results))?;
+        println!("Updated {}", options.baseline);
+    }
+    Ok(regressions.is_empty())
+}
+
+// Prints a random input of a day
+fn run_gen(args: &[String]) -> Result<bool> {
+    let day = args.first().ok_or_else(|| Error::validation(USAGE))?;
+    let day: u32 = day.parse().map_err(|_| Error::validation(format!("Invalid day `{day}`")))?;
+
+    let mut options = GenOptions::default();
+    let mut rest = args[1..].iter();
+    while let Some(option) = rest.next() {
+        let value = rest.next().ok_or_else(|| Error::validation(format!("Missing value for {option}")))?;
+        let number: u64 = value.parse().map_err(|_| Error::validation(format!("Invalid value `{value}` for {option}")))?;
+        match option.as_str() {
+            "--seed" => options.seed = number,
+            "--size" => options.size = Some(number as usize),
+            "--width" => options.width = Some(number as usize),
+            "--height" => options.height = Some(number as usize),
+            "--density" => options.density = Some(number),
+            "--rules" => options.rules = Some(number as usize),
+            "--starts" => options.starts = Some(number as usize),
+            "--rounds" => options.rounds = Some(number as usize),
+            _ => return Err(Error::validation(format!("Unknown option {option}"))),
+        }
+    }
+
+    print!("{}", gen::generate(day, &options)?);
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let result = match args.get(1).map(|arg| arg.as_str()) {
+        Some("run") => run(&args[2..]),
+        Some("bench") => run_bench(&args[2..]),
+        Some("gen") => run_gen(&args[2..]),
+        _ => Err(Error::validation(USAGE)),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error_message) => {
+            eprintln!("ERROR: {error_message}");
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,34 @@
+use std::fs;
+
+use aoc::days;
+
+const ANSWERS: &str = include_str!("../../answers.txt");
+
+#[test]
+fn recorded_answers() {
+    let mut failures: Vec<String> = Vec::new();
+    for line in ANSWERS.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let elements: Vec<&str> = line.split_whitespace().collect();
+        assert_eq!(elements.len(), 3, "Invalid answers line `{line}`");
+        let day: u32 = elements[0].parse().expect("Invalid day");
+        let part: u8 = elements[1].parse().expect("Invalid part");
+        let expected = elements[2];
+
+        let solver = days::find(day).unwrap_or_else(|| panic!("Day {day} has no solver"));
+        let input_path = format!("{}/../day{day}/input.txt", env!("CARGO_MANIFEST_DIR"));
+        let input = fs::read_to_string(&input_path).unwrap_or_else(|e| panic!("Failed to read {input_path}: {e}"));
+        let report = solver(&input, Some(part)).unwrap_or_else(|e| panic!("Day {day} failed to parse: {e}"));
+        match &report.parts[0].answer {
+            Ok(answer) if answer == expected => (),
+            Ok(answer) => failures.push(format!("day {day} part {part}: expected {expected} but got {answer}")),
+            Err(e) => failures.push(format!("day {day} part {part}: expected {expected} but failed with {e}")),
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
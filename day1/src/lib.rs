@@ -0,0 +1,264 @@
+use std::io::BufRead;
+
+use aoc_common::{lines, read_input, read_lines, Error, Result, Rng, Solution};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mode {
+    // Part 1, only the digit characters count
+    Digits,
+    // Part 2, spelled out numbers like "one" count as digits too
+    DigitsAndWords,
+}
+
+// The words for the digits 1 to 9, in that order. Words are kept as chars so matching
+// works the same for any script, not only ASCII.
+pub struct Vocabulary {
+    words: Vec<Vec<char>>,
+}
+
+impl Vocabulary {
+    pub fn from_words(words: &[&str]) -> Result<Self> {
+        if words.len() != 9 {
+            return Err(Error::validation(format!("A vocabulary needs exactly 9 words but got {}", words.len())));
+        }
+
+        let mut result = Self { words: Vec::with_capacity(9) };
+        for word in words {
+            let word: Vec<char> = word.trim().chars().collect();
+            if word.is_empty() || !word.iter().all(|ch| ch.is_alphabetic()) {
+                return Err(Error::validation(format!("Invalid vocabulary word `{}`", word.iter().collect::<String>())));
+            }
+            result.words.push(word);
+        }
+        Ok(result)
+    }
+
+    pub fn from_locale(locale: &str) -> Result<Self> {
+        match locale {
+            "en" => Self::from_words(&[ "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", ]),
+            "id" => Self::from_words(&[ "satu", "dua", "tiga", "empat", "lima", "enam", "tujuh", "delapan", "sembilan", ]),
+            _ => Err(Error::validation(format!("Unknown locale `{locale}`, available locales are en and id"))),
+        }
+    }
+
+    // A vocabulary file has one word per line, the first line is the word for 1 and the
+    // last one is the word for 9. Blank lines are ignored.
+    pub fn from_file(file_path: &str) -> Result<Self> {
+        let file_content = read_input(file_path)?;
+        let mut words: Vec<&str> = Vec::new();
+        for line in lines(&file_content) {
+            let word = line.text.trim();
+            if word.is_empty() {
+                continue;
+            }
+            if !word.chars().all(|ch| ch.is_alphabetic()) {
+                return Err(line.error("Invalid vocabulary word", word));
+            }
+            words.push(word);
+        }
+        Self::from_words(&words)
+    }
+}
+
+// A trie over the reversed vocabulary words. Walking it backward from some position tells
+// which words end at that position, so both the first and the last digit token can be
+// found by looking at every position once, each walk being no longer than the longest word.
+struct TrieNode {
+    children: Vec<(char, usize)>,
+    digit: Option<i32>,
+}
+
+pub struct Scanner {
+    nodes: Vec<TrieNode>,
+    chars: Vec<char>,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let mut result = Self {
+            nodes: vec![TrieNode { children: Vec::new(), digit: None }],
+            chars: Vec::new(),
+        };
+
+        for (i, word) in vocabulary.words.iter().enumerate() {
+            let mut current = 0;
+            for ch in word.iter().rev() {
+                current = match result.nodes[current].children.iter().find(|(c, _)| c == ch) {
+                    Some((_, next)) => *next,
+                    None => {
+                        result.nodes.push(TrieNode { children: Vec::new(), digit: None });
+                        let next = result.nodes.len() - 1;
+                        result.nodes[current].children.push((*ch, next));
+                        next
+                    },
+                };
+            }
+            // Same word twice keeps the smallest digit, like checking the words in order does
+            let digit = 1 + i as i32;
+            let node = &mut result.nodes[current];
+            node.digit = Some(node.digit.map_or(digit, |d| d.min(digit)));
+        }
+
+        result
+    }
+
+    // The digit of the token that ends exactly at `end`. When several words end there the
+    // smallest digit wins.
+    fn token_ending_at(&self, end: usize, mode: Mode) -> Option<i32> {
+        if let Some(digit) = self.chars[end].to_digit(10) {
+            return Some(digit as i32);
+        }
+        if mode == Mode::Digits {
+            return None;
+        }
+
+        let mut result: Option<i32> = None;
+        let mut current = 0;
+        for ch in self.chars[..=end].iter().rev() {
+            match self.nodes[current].children.iter().find(|(c, _)| c == ch) {
+                Some((_, next)) => current = *next,
+                None => break,
+            }
+            if let Some(digit) = self.nodes[current].digit {
+                result = Some(result.map_or(digit, |d| d.min(digit)));
+            }
+        }
+        result
+    }
+
+    pub fn calibration_value(&mut self, line: &str, mode: Mode) -> Option<i32> {
+        // Anything that is neither a digit nor a letter is skipped, so it never breaks a word
+        self.chars.clear();
+        self.chars.extend(line.chars().filter(|ch| ch.is_ascii_digit() || ch.is_alphabetic()));
+
+        let first = (0..self.chars.len()).find_map(|end| self.token_ending_at(end, mode))?;
+        let last = (0..self.chars.len()).rev().find_map(|end| self.token_ending_at(end, mode))?;
+        Some(first * 10 + last)
+    }
+}
+
+// The sums of the calibration values of both parts, one line at a time
+pub struct Calibration {
+    scanner: Scanner,
+    pub part_1: i32,
+    pub part_2: i32,
+}
+
+impl Calibration {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        Self { scanner: Scanner::new(vocabulary), part_1: 0, part_2: 0 }
+    }
+
+    pub fn add_line(&mut self, line: &str) {
+        self.part_1 += self.scanner.calibration_value(line, Mode::Digits).unwrap_or(0);
+        self.part_2 += self.scanner.calibration_value(line, Mode::DigitsAndWords).unwrap_or(0);
+    }
+}
+
+// Sums the calibration values of every line, for both parts
+pub fn solve(input: &str, vocabulary: &Vocabulary) -> (i32, i32) {
+    let mut calibration = Calibration::new(vocabulary);
+    for line in input.split('\n') {
+        calibration.add_line(line);
+    }
+    (calibration.part_1, calibration.part_2)
+}
+
+// The sum of the calibration values of a single part
+pub fn sum(input: &str, vocabulary: &Vocabulary, mode: Mode) -> i32 {
+    let mut scanner = Scanner::new(vocabulary);
+    input.split('\n').map(|line| scanner.calibration_value(line, mode).unwrap_or(0)).sum()
+}
+
+// The same as solve without reading the whole input first
+pub fn solve_reader(reader: impl BufRead, path: &str, vocabulary: &Vocabulary) -> Result<(i32, i32)> {
+    let mut calibration = Calibration::new(vocabulary);
+    read_lines(reader, path, |line| {
+        calibration.add_line(line.text);
+        Ok(())
+    })?;
+    Ok((calibration.part_1, calibration.part_2))
+}
+
+// Random calibration lines for `aoc gen`, a mix of letters, digits and the vocabulary's words.
+// Every line has at least one digit so part 1 has a value for it too.
+pub fn random_document(rng: &mut Rng, amount: usize, vocabulary: &Vocabulary) -> String {
+    let mut result = String::new();
+    for _ in 0..amount {
+        let mut line = String::new();
+        let mut has_digit = false;
+        for _ in 0..rng.below(8) + 1 {
+            match rng.below(3) {
+                0 => {
+                    line.push(char::from(b'1' + rng.below(9) as u8));
+                    has_digit = true;
+                },
+                1 => line.extend(rng.choose(&vocabulary.words)),
+                _ => line.extend((0..rng.below(4) + 1).map(|_| char::from(b'a' + rng.below(26) as u8))),
+            }
+        }
+        if !has_digit {
+            let position = line.char_indices().nth(rng.below(line.chars().count() as u64 + 1) as usize).map_or(line.len(), |(i, _)| i);
+            line.insert(position, char::from(b'1' + rng.below(9) as u8));
+        }
+        result += &line;
+        result.push('\n');
+    }
+    return result;
+}
+
+// The puzzle with the English digit words
+pub struct Day1 {
+    input: String,
+    vocabulary: Vocabulary,
+}
+
+impl Solution for Day1 {
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self { input: input.to_string(), vocabulary: Vocabulary::from_locale("en")? })
+    }
+
+    fn part1(&self) -> Result<i32> {
+        Ok(sum(&self.input, &self.vocabulary, Mode::Digits))
+    }
+
+    fn part2(&self) -> Result<i32> {
+        Ok(sum(&self.input, &self.vocabulary, Mode::DigitsAndWords))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let puzzle = Day1::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 142);
+    }
+
+    #[test]
+    fn example2() {
+        let puzzle = Day1::parse(include_str!("../example2.txt")).unwrap();
+        assert_eq!(puzzle.part2().unwrap(), 281);
+    }
+
+    #[test]
+    fn reader_matches_whole_input() {
+        let vocabulary = Vocabulary::from_locale("en").unwrap();
+        let input = include_str!("../example2.txt");
+        assert_eq!(solve_reader(input.as_bytes(), "example2.txt", &vocabulary).unwrap(), solve(input, &vocabulary));
+    }
+
+    #[test]
+    fn parts_match_solve() {
+        let vocabulary = Vocabulary::from_locale("en").unwrap();
+        let input = include_str!("../example2.txt");
+        let (part_1, part_2) = solve(input, &vocabulary);
+        assert_eq!(sum(input, &vocabulary, Mode::Digits), part_1);
+        assert_eq!(sum(input, &vocabulary, Mode::DigitsAndWords), part_2);
+    }
+}
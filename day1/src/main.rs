@@ -1,47 +1,39 @@
-use std::fs;
 use std::env;
+use std::process::ExitCode;
 
-fn solve_file(file_path: &str) -> std::io::Result<i32> {
-    let file_content = fs::read_to_string(file_path)?;
-    let number_words = [ "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", ];
+use aoc_common::{input_path, open_input, Error, Result};
+use day1::{solve_reader, Vocabulary};
 
-    let mut result = 0;
-    for (_i, line) in file_content.split('\n').enumerate() {
-        let mut first: i32 = 0;
-        let mut last: i32 = 0;
-        let mut accum = String::new();
+const USAGE: &str = "Usage: day1 <input|-> [--locale en|id] [--words <file>]";
 
-        for ch in line.chars() {
-            if ch.is_numeric() {
-                last = ch.to_digit(10).expect("This should not be happened") as i32;
-                accum = String::new();
-            } else if ch.is_alphabetic() {
-                accum.push(ch);
-                'check_word: for (i, word) in number_words.iter().enumerate() {
-                    if accum.ends_with(word) {
-                        last = 1 + i as i32;
-                        break 'check_word;
-                    }
-                }
-            }
+fn run(args: &[String]) -> Result<(i32, i32)> {
+    let file_path = input_path(args).map_err(|e| Error::validation(format!("{e}\n{USAGE}")))?;
 
-            first = if first == 0 { last } else { first };
-        }
-
-        if first != 0 {
-            result += first * 10 + last;
-        }
+    let mut vocabulary = Vocabulary::from_locale("en")?;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        let value = options.next().ok_or_else(|| Error::validation(format!("Missing value for {option}")))?;
+        vocabulary = match option.as_str() {
+            "--locale" => Vocabulary::from_locale(value)?,
+            "--words" => Vocabulary::from_file(value)?,
+            _ => return Err(Error::validation(format!("Unknown option {option}"))),
+        };
     }
 
-    Ok(result)
+    solve_reader(open_input(file_path)?, file_path, &vocabulary)
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("Please provide an input file path");
-        return
+    match run(&args) {
+        Ok((part_1, part_2)) => {
+            println!("Result of part 1: {part_1}");
+            println!("Result of part 2: {part_2}");
+            ExitCode::SUCCESS
+        },
+        Err(error_message) => {
+            eprintln!("ERROR: {}", error_message);
+            ExitCode::FAILURE
+        },
     }
-    let result = solve_file(&args[1]).expect("Failed to solve file due to invalid file");
-    println!("The result is {}", result);
 }
@@ -0,0 +1,524 @@
+use std::fmt;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+use aoc_common::{read_input, read_lines, Error, Result, Rng, Solution};
+
+// Splits `text` on every separator char while keeping the byte offset of each piece
+fn pieces(text: &str, is_separator: impl Fn(char) -> bool) -> Vec<(usize, &str)> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    for (i, ch) in text.char_indices() {
+        if is_separator(ch) {
+            result.push((start, &text[start..i]));
+            start = i + ch.len_utf8();
+        }
+    }
+    result.push((start, &text[start..]));
+    result
+}
+
+fn trim_piece((offset, text): (usize, &str)) -> (usize, &str) {
+    let trimmed = text.trim_start();
+    (offset + text.len() - trimmed.len(), trimmed.trim_end())
+}
+
+#[derive(Clone, Copy)]
+pub enum ColorMode<'a> {
+    // Colors that aren't in the given set are an error
+    Strict(&'a ColorSet),
+    // Any color is accepted
+    Lenient,
+}
+
+#[derive(Clone, Default)]
+pub struct ColorSet {
+    // Amount of cubes by color name, a color that isn't here has 0 cubes
+    pub counts: BTreeMap<String, i32>,
+}
+
+impl ColorSet {
+    pub fn new() -> Self {
+        Self { counts: BTreeMap::new() }
+    }
+
+    // The bag from part 1: 12 red, 13 green and 14 blue cubes
+    pub fn default_bound() -> Self {
+        let mut result = ColorSet::new();
+        result.add("red", 12);
+        result.add("green", 13);
+        result.add("blue", 14);
+        result
+    }
+
+    pub fn get(&self, color: &str) -> i32 {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, color: &str, amount: i32) {
+        *self.counts.entry(color.to_string()).or_insert(0) += amount;
+    }
+
+    // A bound file has one or more color sets, one per line. Lines starting with # are comments.
+    pub fn from_file(file_path: &str) -> Result<Self> {
+        let file_content = read_input(file_path)?;
+        return Self::from_bound_text(&file_content);
+    }
+
+    // The content of a bound file. A color can only be given once in the whole file.
+    pub fn from_bound_text(text: &str) -> Result<Self> {
+        let mut result = ColorSet::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            result.parse_into(line, i + 1, 0, ColorMode::Lenient, true)?;
+        }
+        return Ok(result);
+    }
+
+    // A bound given on the command line, where "<color>=<amount>" works too and a color can
+    // only be given once
+    pub fn from_bound_str(text: &str) -> Result<Self> {
+        let mut result = ColorSet::new();
+        result.parse_into(text, 1, 0, ColorMode::Lenient, true)?;
+        return Ok(result);
+    }
+
+    // One draw of a game. `offset` is where `text` starts inside line number `line`, it's
+    // only used for errors.
+    pub fn from_str(text: &str, line: usize, offset: usize, mode: ColorMode) -> Result<Self> {
+        let mut result = ColorSet::new();
+        result.parse_into(text, line, offset, mode, false)?;
+        return Ok(result);
+    }
+
+    fn parse_into(&mut self, text: &str, line: usize, offset: usize, mode: ColorMode, bound: bool) -> Result<()> {
+        for color_element in pieces(text, |c| c == ',') {
+            let (element_offset, element_str) = trim_piece((offset + color_element.0, color_element.1));
+            // An element is either "<amount> <color>" or, for bounds only, "<color>=<amount>"
+            let color_element: Vec<(usize, &str)> = if bound && element_str.contains('=') {
+                pieces(element_str, |c| c == '=').into_iter().rev().map(trim_piece).collect()
+            } else {
+                pieces(element_str, char::is_whitespace)
+                    .into_iter()
+                    .filter(|(_, word)| !word.is_empty())
+                    .collect()
+            };
+
+            if color_element.len() != 2 || color_element[1].1.is_empty() {
+                return Err(Error::parse("Invalid color set", line, element_offset + 1, element_str));
+            }
+
+            let (amount_offset, amount_str) = color_element[0];
+            let (color_offset, color) = color_element[1];
+            let amount = amount_str.parse::<i32>()
+                .map_err(|_| Error::parse("Failed to parse the amount of color", line, element_offset + amount_offset + 1, amount_str))?;
+            if amount < 0 {
+                return Err(Error::parse("Negative amount of color", line, element_offset + amount_offset + 1, amount_str));
+            }
+            if let ColorMode::Strict(known) = mode {
+                if !known.counts.contains_key(color) {
+                    return Err(Error::parse("Unknown color", line, element_offset + color_offset + 1, color));
+                }
+            }
+            if bound && self.counts.contains_key(color) {
+                return Err(Error::parse("Color is already in the bound", line, element_offset + color_offset + 1, color));
+            }
+            self.add(color, amount);
+        }
+
+        return Ok(());
+    }
+}
+
+impl fmt::Display for ColorSet {
+    // Written in the same grammar `ColorSet::from_str` reads, e.g. "14 blue, 13 green, 12 red"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (color, amount)) in self.counts.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{amount} {color}")?;
+        }
+        Ok(())
+    }
+}
+
+// One color of one draw that didn't fit in the bag
+pub struct Violation {
+    // 0-based index of the draw inside the game
+    pub draw: usize,
+    pub color: String,
+    pub amount: i32,
+    pub limit: i32,
+}
+
+impl Violation {
+    pub fn excess(&self) -> i32 {
+        self.amount - self.limit
+    }
+}
+
+pub struct Game {
+    pub id: i32,
+    pub color_sets: Vec<ColorSet>
+}
+
+impl Game {
+    fn new() -> Self {
+        Self { id: 0, color_sets: vec![], }
+    }
+
+    pub fn inbound(&self, bound: &ColorSet) -> bool {
+        for color_set in &self.color_sets {
+            for (color, amount) in &color_set.counts {
+                if bound.get(color) < *amount {
+                    return false;
+                }
+            }
+        }
+        return true;
+    }
+
+    // Every draw and color that breaks the bound, empty when the game is possible
+    pub fn explain(&self, bound: &ColorSet) -> Vec<Violation> {
+        let mut result = Vec::new();
+        for (draw, color_set) in self.color_sets.iter().enumerate() {
+            for (color, amount) in &color_set.counts {
+                let limit = bound.get(color);
+                if limit < *amount {
+                    result.push(Violation { draw, color: color.clone(), amount: *amount, limit });
+                }
+            }
+        }
+        result
+    }
+
+    // The least amount of cubes of every color that has to be in the bag for this game
+    pub fn least_color_set(&self) -> ColorSet {
+        let mut result = ColorSet::new();
+        for color_set in &self.color_sets {
+            for (color, amount) in &color_set.counts {
+                let least = result.counts.entry(color.clone()).or_insert(0);
+                if *least < *amount {
+                    *least = *amount;
+                }
+            }
+        }
+        result
+    }
+
+    // The product of the least color set, an error when it doesn't fit in an i32
+    pub fn power(&self) -> Result<i32> {
+        let mut result: i32 = 1;
+        for amount in self.least_color_set().counts.values() {
+            result = result.checked_mul(*amount)
+                .ok_or_else(|| Error::validation(format!("The power of game {} is too large", self.id)))?;
+        }
+        return Ok(result);
+    }
+
+    pub fn from_line_str(line_str: &str, line: usize, mode: ColorMode) -> Result<Self> {
+        let elements = pieces(line_str, |c| c == ':');
+        let mut game = Game::new();
+        if elements.len() != 2 {
+            return Err(Error::parse("Invalid game line", line, 1, line_str));
+        }
+
+        let label_elements: Vec<(usize, &str)> = pieces(elements[0].1, char::is_whitespace)
+            .into_iter()
+            .filter(|(_, word)| !word.is_empty())
+            .collect();
+        if label_elements.len() != 2 {
+            return Err(Error::parse("Invalid label element", line, 1, elements[0].1));
+        }
+
+        let (game_offset, game_str) = label_elements[0];
+        if game_str != "Game" {
+            return Err(Error::parse("Label element doesn't starts with `Game`", line, game_offset + 1, game_str));
+        }
+
+        let (id_offset, id_str) = label_elements[1];
+        game.id = id_str.parse::<i32>()
+            .map_err(|_| Error::parse("Failed to parse game id", line, id_offset + 1, id_str))?;
+
+        let (sets_offset, sets_str) = elements[1];
+        for (set_offset, set_str) in pieces(sets_str, |c| c == ';') {
+            game.color_sets.push(ColorSet::from_str(set_str, line, sets_offset + set_offset, mode)?);
+        }
+
+        Ok(game)
+    }
+}
+
+// Parses the games one line at a time and hands each of them to `f` without keeping them
+pub fn for_each_game(reader: impl BufRead, path: &str, mode: ColorMode, mut f: impl FnMut(Game) -> Result<()>) -> Result<()> {
+    read_lines(reader, path, |line| {
+        if line.text.trim().is_empty() {
+            return Ok(());
+        }
+        f(Game::from_line_str(line.text, line.number, mode)?)
+    })
+}
+
+pub fn parse_games(input: &str, mode: ColorMode) -> Result<Vec<Game>> {
+    let mut games = Vec::new();
+    for_each_game(input.as_bytes(), "input", mode, |game| {
+        games.push(game);
+        Ok(())
+    })?;
+    Ok(games)
+}
+
+// Adds the power of `game` to `sum`, an error when the sum doesn't fit in an i32
+pub fn add_power(sum: i32, game: &Game) -> Result<i32> {
+    return sum.checked_add(game.power()?)
+        .ok_or_else(|| Error::validation("The sum of power is too large"));
+}
+
+// Part 1, the sum of the ids of the games that fit in the bound
+pub fn sum_of_id(games: &[Game], boundary_for_part_1: &ColorSet) -> i32 {
+    let mut result = 0;
+    for game in games {
+        if game.inbound(boundary_for_part_1) {
+            // print!("Game {}: ", game.id);
+            // for color_set in &game.color_sets {
+            //     print!("{}; ", color_set);
+            // }
+            // println!();
+            result += game.id;
+        }
+    }
+    result
+}
+
+// Part 2, the sum of the power of every game
+pub fn sum_of_power(games: &[Game]) -> Result<i32> {
+    let mut result = 0;
+    for game in games {
+        result = add_power(result, game)?;
+    }
+    Ok(result)
+}
+
+// Branch and bound over the amounts of each color. `games` are the games that still fit
+// with the amounts picked so far, and only amounts some game actually needs are tried.
+fn search_bound(needs: &[Vec<i32>], required: usize, games: Vec<usize>, current: &mut Vec<i32>, total: i32, best: &mut Option<(i32, Vec<i32>)>) {
+    if games.len() < required {
+        return;
+    }
+
+    let color = current.len();
+    if color == needs.first().map_or(0, |need| need.len()) {
+        if best.as_ref().is_none_or(|(best_total, _)| total < *best_total) {
+            *best = Some((total, current.clone()));
+        }
+        return;
+    }
+
+    let mut candidates: Vec<i32> = games.iter().map(|&game| needs[game][color]).collect();
+    candidates.push(0);
+    candidates.sort();
+    candidates.dedup();
+
+    for amount in candidates {
+        if let Some((best_total, _)) = best {
+            if total + amount >= *best_total {
+                break;
+            }
+        }
+
+        let kept: Vec<usize> = games.iter().copied().filter(|&game| needs[game][color] <= amount).collect();
+        if kept.len() < required {
+            continue;
+        }
+
+        current.push(amount);
+        search_bound(needs, required, kept, current, total + amount, best);
+        current.pop();
+    }
+}
+
+// The bound with the least total amount of cubes under which at least `fraction` of the
+// games are possible. Ties go to the bound that is smaller in the earlier colors.
+pub fn minimum_bound(games: &[Game], fraction: f64) -> ColorSet {
+    let least_color_sets: Vec<ColorSet> = games.iter().map(|game| game.least_color_set()).collect();
+    let mut colors: Vec<&String> = least_color_sets.iter().flat_map(|set| set.counts.keys()).collect();
+    colors.sort();
+    colors.dedup();
+
+    let needs: Vec<Vec<i32>> = least_color_sets.iter()
+        .map(|set| colors.iter().map(|color| set.get(color)).collect())
+        .collect();
+    let required = (fraction.clamp(0.0, 1.0) * games.len() as f64).ceil() as usize;
+
+    let mut best = None;
+    search_bound(&needs, required, (0..games.len()).collect(), &mut Vec::new(), 0, &mut best);
+
+    let mut result = ColorSet::new();
+    if let Some((_, amounts)) = best {
+        for (color, amount) in colors.iter().zip(amounts) {
+            result.add(color, amount);
+        }
+    }
+    result
+}
+
+// Random games for `aoc gen`, every draw has at least one of the colors
+pub fn random_games(rng: &mut Rng, amount: usize, max_draws: usize, max_cubes: i32) -> Vec<Game> {
+    let mut games = Vec::new();
+    for id in 1..=amount {
+        let mut game = Game { id: id as i32, color_sets: Vec::new() };
+        for _ in 0..rng.below(max_draws.max(1) as u64) + 1 {
+            let mut color_set = ColorSet::new();
+            while color_set.counts.is_empty() {
+                for color in ["red", "green", "blue"] {
+                    if rng.chance(2, 3) {
+                        color_set.add(color, rng.range(1..max_cubes.max(1) as i64 + 1) as i32);
+                    }
+                }
+            }
+            game.color_sets.push(color_set);
+        }
+        games.push(game);
+    }
+    games
+}
+
+pub fn games_to_input(games: &[Game]) -> String {
+    let mut result = String::new();
+    for game in games {
+        let draws: Vec<String> = game.color_sets.iter().map(|color_set| color_set.to_string()).collect();
+        result += &format!("Game {}: {}\n", game.id, draws.join("; "));
+    }
+    result
+}
+
+pub struct Day2 {
+    games: Vec<Game>,
+    bound: ColorSet,
+}
+
+impl Solution for Day2 {
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self { games: parse_games(input, ColorMode::Lenient)?, bound: ColorSet::default_bound() })
+    }
+
+    fn part1(&self) -> Result<i32> {
+        Ok(sum_of_id(&self.games, &self.bound))
+    }
+
+    fn part2(&self) -> Result<i32> {
+        sum_of_power(&self.games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let puzzle = Day2::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 8);
+        assert_eq!(puzzle.part2().unwrap(), 2286);
+    }
+
+    #[test]
+    fn error_spans() {
+        let error = Game::from_line_str("Game 1: 3 blue, x4 red", 1, ColorMode::Lenient).err().unwrap();
+        assert_eq!(error.to_string(), "line 1, column 17: Failed to parse the amount of color `x4`");
+        assert_eq!(error.span(), Some(16..18));
+
+        let bound = ColorSet::default_bound();
+        let error = Game::from_line_str("Game 2: 1 red; 2 purple", 2, ColorMode::Strict(&bound)).err().unwrap();
+        assert_eq!(error.to_string(), "line 2, column 18: Unknown color `purple`");
+        assert_eq!(error.span(), Some(17..23));
+    }
+
+    #[test]
+    fn power_overflow() {
+        let game = Game::from_line_str("Game 1: 99999 red, 99999 green, 99999 blue", 1, ColorMode::Lenient).unwrap();
+        assert_eq!(game.power().err().unwrap().to_string(), "The power of game 1 is too large");
+
+        let game = Game::from_line_str("Game 2: 46340 red, 46340 green", 1, ColorMode::Lenient).unwrap();
+        assert_eq!(game.power().unwrap(), 46340 * 46340);
+        assert!(add_power(game.power().unwrap(), &game).is_err());
+    }
+
+    #[test]
+    fn negative_amount() {
+        let error = ColorSet::from_str("3 red, -2 blue", 1, 0, ColorMode::Lenient).err().unwrap();
+        assert_eq!(error.to_string(), "line 1, column 8: Negative amount of color `-2`");
+    }
+
+    #[test]
+    fn color_modes() {
+        let bound = ColorSet::default_bound();
+        let known = "Game 1: 3 blue, 4 red; 2 green";
+        let unknown = "Game 2: 1 red; 2 purple";
+
+        assert!(Game::from_line_str(known, 1, ColorMode::Strict(&bound)).is_ok());
+        assert!(Game::from_line_str(unknown, 2, ColorMode::Strict(&bound)).is_err());
+
+        assert!(Game::from_line_str(known, 1, ColorMode::Lenient).is_ok());
+        let game = Game::from_line_str(unknown, 2, ColorMode::Lenient).unwrap();
+        assert_eq!(game.color_sets[1].get("purple"), 2);
+        // Lenient games can still be checked, a color the bound doesn't have has 0 cubes
+        assert!(!game.inbound(&bound));
+    }
+
+    #[test]
+    fn explain() {
+        let bound = ColorSet::default_bound();
+        let possible = Game::from_line_str("Game 1: 12 red, 13 green; 14 blue", 1, ColorMode::Strict(&bound)).unwrap();
+        assert!(possible.explain(&bound).is_empty());
+
+        let impossible = Game::from_line_str("Game 2: 1 red; 15 blue, 2 green; 13 red", 2, ColorMode::Strict(&bound)).unwrap();
+        let violations = impossible.explain(&bound);
+        let found: Vec<(usize, &str, i32, i32)> = violations.iter()
+            .map(|violation| (violation.draw, violation.color.as_str(), violation.amount, violation.excess()))
+            .collect();
+        assert_eq!(found, vec![(1, "blue", 15, 1), (2, "red", 13, 1)]);
+    }
+
+    #[test]
+    fn bounds() {
+        let bound = ColorSet::from_bound_str("red=1, 2 green").unwrap();
+        assert_eq!(bound.to_string(), "2 green, 1 red");
+        let error = ColorSet::from_bound_str("red=1, 2 red").err().unwrap();
+        assert_eq!(error.to_string(), "line 1, column 10: Color is already in the bound `red`");
+
+        let bound = ColorSet::from_bound_text("# the bag\n3 red\n\nblue = 4, 5 green\n").unwrap();
+        assert_eq!(bound.to_string(), "4 blue, 5 green, 3 red");
+        let error = ColorSet::from_bound_text("3 red\n4 red\n").err().unwrap();
+        assert_eq!(error.to_string(), "line 2, column 3: Color is already in the bound `red`");
+
+        // Game draws only have the "<amount> <color>" form
+        assert!(Game::from_line_str("Game 1: red=3", 1, ColorMode::Lenient).is_err());
+    }
+
+    #[test]
+    fn minimum_bound_of_the_example() {
+        let games = parse_games(include_str!("../example.txt"), ColorMode::Lenient).unwrap();
+        let bound = minimum_bound(&games, 1.0);
+        assert_eq!(bound.to_string(), "15 blue, 13 green, 20 red");
+        assert!(games.iter().all(|game| game.inbound(&bound)));
+
+        let bound = minimum_bound(&games, 0.6);
+        assert_eq!(bound.to_string(), "6 blue, 3 green, 6 red");
+        assert_eq!(games.iter().filter(|game| game.inbound(&bound)).count(), 3);
+
+        assert_eq!(minimum_bound(&games, 0.0).to_string(), "0 blue, 0 green, 0 red");
+    }
+    #[test]
+    fn part_1_without_part_2() {
+        let puzzle = Day2::parse("Game 1: 1 red\nGame 2: 99999 red, 99999 green, 99999 blue\n").unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 1);
+        assert!(puzzle.part2().is_err());
+    }
+}
@@ -1,155 +1,93 @@
-use std::fs;
 use std::env;
-
-#[derive(Clone)]
-struct ColorSet {
-    red: i32,
-    green: i32,
-    blue: i32,
-}
-
-impl ColorSet {
-    fn new() -> Self {
-        Self { red: 0, green: 0, blue: 0 }
-    }
-
-    fn from_str(text: &str) -> Result<Self, &'static str> { let mut result = ColorSet::new();
-        for color_element_str in text.split(",") {
-            let color_element: Vec<&str> = color_element_str.trim().split_whitespace().collect();
-
-            if color_element.len() == 2 {
-                if let Ok(amount) = color_element[0].parse::<i32>() {
-                    match color_element[1] {
-                        "red" => result.red += amount,
-                        "green" => result.green += amount,
-                        "blue" => result.blue += amount,
-                        _ => (),
-                    }
+use std::process::ExitCode;
+
+use aoc_common::{input_path, open_input, Error, Result};
+use day2::{add_power, for_each_game, minimum_bound, ColorMode, ColorSet, Game};
+
+const USAGE: &str = "Usage: day2 <input|-> [--bound <color set> | --bound-file <file>] [--strict] [--explain] [--min-bound <fraction>]";
+
+fn run(args: &[String]) -> Result<(i32, Result<i32>)> {
+    let file_path = input_path(args).map_err(|e| Error::validation(format!("{e}\n{USAGE}")))?;
+
+    let mut bound = ColorSet::default_bound();
+    let mut strict = false;
+    let mut explain = false;
+    let mut min_bound_fraction: Option<f64> = None;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--strict" => strict = true,
+            "--explain" => explain = true,
+            "--bound" | "--bound-file" => {
+                let value = options.next().ok_or_else(|| Error::validation(format!("Missing value for {option}")))?;
+                bound = if option == "--bound" {
+                    ColorSet::from_bound_str(value)?
                 } else {
-                    return Err("Failed to parse the amount of color");
+                    ColorSet::from_file(value)?
+                };
+            },
+            "--min-bound" => {
+                match options.next().map(|value| value.parse::<f64>()) {
+                    Some(Ok(fraction)) if (0.0..=1.0).contains(&fraction) => min_bound_fraction = Some(fraction),
+                    _ => return Err(Error::validation("--min-bound expects a fraction between 0 and 1")),
                 }
-            } else {
-                return Err("Invalid color set");
-            }
+            },
+            _ => return Err(Error::validation(format!("Unknown option {option}"))),
         }
-
-        return Ok(result);
-    }
-
-    // fn to_string(&self) -> String {
-    //     let mut result = String::new();
-    //     result.push_str(&self.red.to_string());
-    //     result.push_str(" red, ");
-    //     result.push_str(&self.green.to_string());
-    //     result.push_str(" green, ");
-    //     result.push_str(&self.blue.to_string());
-    //     result.push_str(" blue; ");
-    //     return result;
-    // }
-}
-
-struct Game {
-    id: i32,
-    color_sets: Vec<ColorSet>
-}
-
-impl Game {
-    fn new() -> Self {
-        Self { id: 0, color_sets: vec![], }
     }
 
-    fn inbound(&self, bound: &ColorSet) -> bool {
-        for color_set in &self.color_sets {
-            if bound.red < color_set.red || bound.green < color_set.green || bound.blue < color_set.blue {
-                return false;
+    let mode = if strict { ColorMode::Strict(&bound) } else { ColorMode::Lenient };
+    let mut sum_of_id = 0;
+    // Part 1 is still reported when the power doesn't fit
+    let mut sum_of_power: Result<i32> = Ok(0);
+    // Only the smallest bound needs every game at once, everything else goes game by game
+    let mut games: Vec<Game> = Vec::new();
+    for_each_game(open_input(file_path)?, file_path, mode, |game| {
+        if explain {
+            for violation in game.explain(&bound) {
+                println!("Game {} is impossible: draw {} has {} {}, {} more than the bound of {}",
+                    game.id, violation.draw + 1, violation.amount, violation.color, violation.excess(), violation.limit);
             }
         }
-        return true;
-    }
 
-    fn power(&self) -> i32 {
-        let mut least_color_set = ColorSet::new();
-        for color_set in &self.color_sets {
-            if least_color_set.red < color_set.red {
-                least_color_set.red = color_set.red;
-            }
-            if least_color_set.green < color_set.green {
-                least_color_set.green = color_set.green;
-            }
-            if least_color_set.blue < color_set.blue {
-                least_color_set.blue = color_set.blue;
-            }
+        if game.inbound(&bound) {
+            sum_of_id += game.id;
         }
-        return least_color_set.red * least_color_set.green * least_color_set.blue;
-    }
-
-    fn from_line_str(line: &str) -> Result<Self, &'static str> {
-        let elements: Vec<&str> = line.split(":").collect();
-        let mut game = Game::new();
-        if elements.len() == 2 {
-            let label_elements: Vec<&str> = elements[0].split_whitespace().collect();
-            if label_elements.len() != 2 {
-                return Err("Invalid label element");
-            }
-
-            if label_elements[0] != "Game" {
-                return Err("Label element doesn't starts with `Game`");
-            }
-
-            if let Ok(id) = label_elements[1].parse::<i32>() {
-                game.id = id;
-            } else {
-                return Err("Failed to parse game id");
-            }
-
-            for set_element in elements[1].split(";") {
-                game.color_sets.push(ColorSet::from_str(set_element)?);
-            }
-
-            Ok(game)
-        } else {
-            return Err("Invalid game line");
+        if let Ok(sum) = sum_of_power {
+            sum_of_power = add_power(sum, &game);
         }
-    }
-}
-
-fn solve_file(file_path: &str, boundary_for_part_1: ColorSet) -> Result<(i32, i32), &'static str> {
-    if let Ok(file_content) = fs::read_to_string(file_path) {
-        let mut sum_of_id = 0;
-        let mut sum_of_power = 0;
-        for line in file_content.trim().split("\n") {
-            let game = Game::from_line_str(line)?;
-            if game.inbound(&boundary_for_part_1) {
-                // print!("Game {}: ", game.id);
-                // for color_set in &game.color_sets {
-                //     print!("{}", color_set.to_string());
-                // }
-                // println!();
-                sum_of_id += game.id;
-            }
-            sum_of_power += game.power();
+        if min_bound_fraction.is_some() {
+            games.push(game);
         }
-        Ok((sum_of_id, sum_of_power))
-    } else {
-        Err("Failed to read file")
+        Ok(())
+    })?;
+
+    if let Some(fraction) = min_bound_fraction {
+        println!("The smallest bound for {}% of the games is {}", fraction * 100.0, minimum_bound(&games, fraction));
     }
+
+    Ok((sum_of_id, sum_of_power))
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("Please provide an input file path");
-        return
-    }
-
-    let bound = ColorSet { red: 12, green: 13, blue: 14 };
-    match solve_file(&args[1], bound) {
+    match run(&args) {
         Ok((part_1_answer, part_2_answer)) => {
             println!("The sum of id for part 1 is {part_1_answer}");
-            println!("The sum of power for part 2 is {part_2_answer}");
+            match part_2_answer {
+                Ok(part_2_answer) => {
+                    println!("The sum of power for part 2 is {part_2_answer}");
+                    ExitCode::SUCCESS
+                },
+                Err(msg) => {
+                    eprintln!("ERROR: {}", msg);
+                    ExitCode::FAILURE
+                },
+            }
         },
         Err(msg) => {
-            eprintln!("{}", msg);
-        }
+            eprintln!("ERROR: {}", msg);
+            ExitCode::FAILURE
+        },
     }
 }
@@ -0,0 +1,283 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::{lines, Error, Grid, Result, Rng, Solution};
+
+#[derive(Debug, Clone)]
+enum Token {
+    Dot,
+    Asterisk,
+    Symbol(char),
+    Number(i32),
+    Ref(i32, i32),
+}
+
+pub struct EngineSchema {
+    grid: Grid<Token>,
+}
+
+impl FromStr for EngineSchema {
+    type Err = Error;
+
+    fn from_str(data: &str) -> Result<Self> {
+        let mut result = Self { grid: Grid::new() };
+
+        for line in lines(data) {
+            let line_number = line.number;
+            let line: Vec<char> = line.text.chars().collect();
+            let mut row: Vec<Token> = Vec::with_capacity(line.len());
+            let mut i = 0;
+            while i < line.len() {
+                let ch = line[i];
+                match ch {
+                    '.' => {
+                        row.push(Token::Dot);
+                        i += 1;
+                    }
+                    '*' => {
+                        row.push(Token::Asterisk);
+                        i += 1;
+                    }
+                    _ if ch.is_ascii_digit() => {
+                        let mut accum = String::new();
+                        while i < line.len() && line[i].is_ascii_digit() {
+                            accum.push(line[i]);
+                            i += 1;
+                        }
+                        if let Ok(num) = accum.parse::<i32>() {
+                            let number_row = result.grid.rows;
+                            let number_col = (i - accum.len()) as i32;
+                            row.push(Token::Number(num));
+                            for _ in 1..accum.len() {
+                                row.push(Token::Ref(number_row, number_col));
+                            }
+                        } else {
+                            return Err(Error::parse("Failed to parse number", line_number, i - accum.len() + 1, &accum));
+                        }
+                    },
+                    _ => {
+                        row.push(Token::Symbol(ch));
+                        i += 1;
+                    },
+                }
+            }
+
+            result.grid.push_row(row)?;
+        }
+
+        return Ok(result);
+    }
+}
+
+impl EngineSchema {
+    fn count_sign_nbors(&self, row: i32, col: i32) -> i32 {
+        if !self.grid.contains(row, col) {
+            return 0;
+        }
+
+        let mut result: i32 = 0;
+
+        for (nrow, ncol) in self.grid.neighbours(row, col) {
+            match self.grid.data[self.grid.index_of(nrow, ncol)] {
+                Token::Dot => (),
+                Token::Symbol(_) => result += 1,
+                Token::Asterisk => result += 1,
+                Token::Number(_) => (),
+                Token::Ref(_, _) => (),
+            }
+        }
+
+        return result;
+    }
+
+    fn number_nbors_info(&self, row: i32, col: i32) -> (usize, i32) {
+        if !self.grid.contains(row, col) {
+            return (0, 0);
+        }
+
+        let mut amount: usize = 0;
+        let mut ratio = 1;
+
+        // Numbers are told apart by where they start, two of them can have the same value
+        let mut used_number: Vec<(i32, i32)> = vec![];
+
+        for (nrow, ncol) in self.grid.neighbours(row, col) {
+            let token = &self.grid.data[self.grid.index_of(nrow, ncol)];
+            let position = match token {
+                Token::Number(_) => (nrow, ncol),
+                Token::Ref(rrow, rcol) => (*rrow, *rcol),
+                _ => continue,
+            };
+            if let Token::Number(val) = &self.grid.data[self.grid.index_of(position.0, position.1)] {
+                if !used_number.contains(&position) {
+                    amount += 1;
+                    ratio *= val;
+                    used_number.push(position);
+                }
+            }
+        }
+
+        return (amount, ratio);
+    }
+
+    // The sum of the part numbers and the sum of the gear ratios
+    pub fn sums(&self) -> Result<(i32, i32)> {
+        let mut current_number = 0;
+        let mut sign_nbors_amount = 0;
+        let mut sum_of_valid_numbers = 0;
+
+        let mut sum_of_asterisk_gears_ratio = 0;
+
+        for row in 0..self.grid.rows {
+            for col in 0..self.grid.cols {
+                let token = &self.grid.data[self.grid.index_of(row, col)];
+                match token {
+                    Token::Number(value) => {
+                        if current_number == 0 {
+                            current_number = *value;
+                            sign_nbors_amount = self.count_sign_nbors(row, col);
+                        } else {
+                            return Err(Error::validation("Unreachable conditions: current_number should be 0 when token is Token::Number"));
+                        }
+                    }
+                    Token::Ref(ref_row, ref_col) => {
+                        let referenced_token = &self.grid.data[self.grid.index_of(*ref_row, *ref_col)];
+                        match referenced_token {
+                            Token::Number(_) => {
+                                sign_nbors_amount += self.count_sign_nbors(row, col);
+                            }
+                            _ => {
+                                return Err(Error::validation("Unreachable conditions expecting Token::Ref only referencing to Token::Number"));
+                            },
+                        }
+                    },
+                    Token::Asterisk => {
+                        if sign_nbors_amount > 0 {
+                            sum_of_valid_numbers += current_number;
+                        }
+                        current_number = 0;
+                        sign_nbors_amount = 0;
+
+                        let (amount, ratio) = self.number_nbors_info(row, col);
+                        if amount == 2 {
+                            sum_of_asterisk_gears_ratio += ratio;
+                        }
+                    },
+                    _ => {
+                        if sign_nbors_amount > 0 {
+                            sum_of_valid_numbers += current_number;
+                        }
+                        current_number = 0;
+                        sign_nbors_amount = 0;
+                    },
+                }
+
+                // println!("({row},{col}) current_number = {current_number} | nbors = {sign_nbors_amount}");
+            }
+
+            // A number at the end of the row ends there, the next row may start with another one
+            if sign_nbors_amount > 0 {
+                sum_of_valid_numbers += current_number;
+            }
+            current_number = 0;
+            sign_nbors_amount = 0;
+        }
+
+        return Ok((sum_of_valid_numbers, sum_of_asterisk_gears_ratio));
+    }
+}
+
+impl fmt::Display for EngineSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.grid.rows {
+            for col in 0..self.grid.cols {
+                match &self.grid.data[self.grid.index_of(row, col)] {
+                    Token::Dot => write!(f, ".")?,
+                    Token::Asterisk => write!(f, "*")?,
+                    Token::Symbol(ch) => write!(f, "{ch}")?,
+                    Token::Number(num) => write!(f, "{num}")?,
+                    Token::Ref(row, col) => write!(f, "({row}{col})")?,
+                }
+                write!(f, ", ")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// A random schematic for `aoc gen`. `density` is the percent of cells where a number starts,
+// a third as many cells get a symbol. Numbers always end before a `.` or a symbol so two of
+// them never join into one.
+pub fn random_schematic(rng: &mut Rng, width: usize, height: usize, density: u64) -> String {
+    const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '%', '=', '-', '&'];
+    let mut result = String::new();
+    for _ in 0..height {
+        let mut row: Vec<char> = Vec::with_capacity(width);
+        while row.len() < width {
+            let roll = rng.below(300);
+            if roll < density * 3 {
+                let length = (rng.below(3) as usize + 1).min(width - row.len());
+                row.push(char::from(b'1' + rng.below(9) as u8));
+                row.extend((1..length).map(|_| char::from(b'0' + rng.below(10) as u8)));
+                if row.len() < width {
+                    row.push('.');
+                }
+            } else if roll < density * 4 {
+                row.push(*rng.choose(&SYMBOLS));
+            } else {
+                row.push('.');
+            }
+        }
+        result.extend(row);
+        result.push('\n');
+    }
+    return result;
+}
+
+pub struct Day3 {
+    schema: EngineSchema,
+}
+
+impl Solution for Day3 {
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self { schema: input.parse()? })
+    }
+
+    fn part1(&self) -> Result<i32> {
+        Ok(self.schema.sums()?.0)
+    }
+
+    fn part2(&self) -> Result<i32> {
+        Ok(self.schema.sums()?.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let puzzle = Day3::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 4361);
+        assert_eq!(puzzle.part2().unwrap(), 467835);
+    }
+
+    #[test]
+    fn number_at_the_end_of_a_row() {
+        let puzzle = Day3::parse("..12\n34*.\n").unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 46);
+        assert_eq!(puzzle.part2().unwrap(), 408);
+    }
+
+    #[test]
+    fn gear_with_equal_numbers() {
+        let puzzle = Day3::parse("5*5\n").unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 10);
+        assert_eq!(puzzle.part2().unwrap(), 25);
+    }
+}
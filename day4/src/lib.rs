@@ -0,0 +1,206 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use aoc_common::{lines, read_lines, Line, Result, Rng, Solution};
+
+#[derive(Debug, Clone)]
+pub struct ScratchPad {
+    pub id: i32,
+    pub winning_numbers: Vec<i32>,
+    pub player_numbers: Vec<i32>,
+}
+
+impl ScratchPad {
+    pub fn new(line: Line) -> Result<Self> {
+        let (label, numbers) = line.text.split_once(':').ok_or_else(|| line.error("Invalid string", line.text))?;
+
+        let mut card_labels = label.split_whitespace();
+        let id_str = match (card_labels.next(), card_labels.next(), card_labels.next()) {
+            (Some(_), Some(id_str), None) => id_str,
+            _ => return Err(line.error("Invalid card label", label)),
+        };
+        let id: i32 = line.parse(id_str)?;
+
+        let (winning, player) = numbers.split_once('|').ok_or_else(|| line.error("Invalid numbers", numbers))?;
+
+        let winning_numbers: Vec<i32> = line.numbers(winning)?;
+        let player_numbers: Vec<i32> = line.numbers(player)?;
+
+        return Ok(Self { id, winning_numbers, player_numbers, });
+    }
+
+    pub fn amount_of_winning_numbers(&self) -> i32 {
+        let mut result = 0;
+        for number in &self.player_numbers {
+            if self.winning_numbers.contains(number) {
+                result += 1;
+            }
+        }
+        return result;
+    }
+
+    pub fn calculate_point(&self) -> i32 {
+        let n = self.amount_of_winning_numbers();
+        if n == 0 {
+            return 0;
+        }
+        let base: i32 = 2;
+        return base.pow((n as u32) - 1);
+    }
+}
+
+// Random cards for the property tests and `aoc gen`, numbered from 1. Each card has up to
+// `max_matches` of its numbers among the winning ones.
+pub fn random_cards(rng: &mut Rng, amount: usize, max_matches: usize) -> Vec<ScratchPad> {
+    let mut cards = Vec::new();
+    for id in 1..=amount {
+        let mut pool: Vec<i32> = (1..100).collect();
+        rng.shuffle(&mut pool);
+        let matches = rng.below(max_matches.min(5) as u64 + 1) as usize;
+        let winning_numbers = pool[..5].to_vec();
+        let mut player_numbers = pool[..matches].to_vec();
+        player_numbers.extend_from_slice(&pool[5..13 - matches]);
+        rng.shuffle(&mut player_numbers);
+        cards.push(ScratchPad { id: id as i32, winning_numbers, player_numbers });
+    }
+    return cards;
+}
+
+pub fn cards_to_input(cards: &[ScratchPad]) -> String {
+    let mut result = String::new();
+    for card in cards {
+        let winning: Vec<String> = card.winning_numbers.iter().map(|number| format!("{number:>2}")).collect();
+        let player: Vec<String> = card.player_numbers.iter().map(|number| format!("{number:>2}")).collect();
+        result += &format!("Card {:>3}: {} | {}\n", card.id, winning.join(" "), player.join(" "));
+    }
+    return result;
+}
+
+// Both parts one card at a time. Only the copies won for the next few cards are kept, a card
+// can't win copies further than its amount of winning numbers.
+pub fn solve_reader(reader: impl BufRead, path: &str) -> Result<(i32, i32)> {
+    let mut points = 0;
+    let mut total_cards = 0;
+    let mut won_copies: VecDeque<i32> = VecDeque::new();
+    read_lines(reader, path, |line| {
+        let card = ScratchPad::new(line)?;
+        points += card.calculate_point();
+
+        let amount = 1 + won_copies.pop_front().unwrap_or(0);
+        total_cards += amount;
+        let won = card.amount_of_winning_numbers() as usize;
+        if won_copies.len() < won {
+            won_copies.resize(won, 0);
+        }
+        for copies in won_copies.iter_mut().take(won) {
+            *copies += amount;
+        }
+        Ok(())
+    })?;
+    Ok((points, total_cards))
+}
+
+pub struct Day4 {
+    pub cards: Vec<ScratchPad>,
+}
+
+impl Day4 {
+    // Every copy of a card wins one more copy of each of the next cards
+    pub fn total_cards_copy_by_copy(&self) -> i32 {
+        let cards = &self.cards;
+        let mut amounts: Vec<i32> = vec![1; cards.len()];
+        let mut total_amount = 0;
+        for i in 0..cards.len() {
+            let amount_of_winning_numbers = cards[i].amount_of_winning_numbers();
+            for _ in 0..amounts[i] {
+                for j in 0..amount_of_winning_numbers {
+                    let index = cards[i].id + j;
+                    if index < (cards.len() as i32) {
+                        amounts[index as usize] += 1;
+                    }
+                }
+                total_amount += 1;
+            }
+        }
+        return total_amount;
+    }
+
+    // The same but all copies of a card win their copies at once
+    pub fn total_cards(&self) -> i32 {
+        let mut amounts: Vec<i32> = vec![1; self.cards.len()];
+        for (i, card) in self.cards.iter().enumerate() {
+            let won = card.amount_of_winning_numbers() as usize;
+            for j in i + 1..(i + 1 + won).min(self.cards.len()) {
+                amounts[j] += amounts[i];
+            }
+        }
+        return amounts.iter().sum();
+    }
+}
+
+impl Solution for Day4 {
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self> {
+        let mut cards: Vec<ScratchPad> = Vec::new();
+        for line in lines(input) {
+            cards.push(ScratchPad::new(line)?);
+        }
+        Ok(Self { cards })
+    }
+
+    fn part1(&self) -> Result<i32> {
+        Ok(self.cards.iter().map(|card| card.calculate_point()).sum())
+    }
+
+    fn part2(&self) -> Result<i32> {
+        Ok(self.total_cards())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{check, shrink_vec};
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let puzzle = Day4::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 13);
+        assert_eq!(puzzle.part2().unwrap(), 30);
+    }
+
+    #[test]
+    fn total_cards_match_counting_copy_by_copy() {
+        check(
+            |rng| { let amount = rng.below(12) as usize; random_cards(rng, amount, 4) },
+            |cards| shrink_vec(cards, |card| {
+                // Fewer matches by dropping one of the player's numbers
+                (0..card.player_numbers.len()).map(|i| {
+                    let mut smaller = card.clone();
+                    smaller.player_numbers.remove(i);
+                    smaller
+                }).collect()
+            }),
+            |cards| {
+                // Numbering the cards again like the puzzle would after some were shrunk away
+                let input = cards_to_input(&renumbered(cards));
+                let puzzle = Day4::parse(&input).unwrap();
+                let expected = puzzle.total_cards_copy_by_copy();
+                puzzle.total_cards() == expected && solve_reader(input.as_bytes(), "input").unwrap().1 == expected
+            },
+        );
+    }
+
+    fn renumbered(cards: &[ScratchPad]) -> Vec<ScratchPad> {
+        cards.iter().enumerate().map(|(i, card)| ScratchPad { id: i as i32 + 1, ..card.clone() }).collect()
+    }
+
+    #[test]
+    fn reader_matches_whole_input() {
+        let input = include_str!("../example.txt");
+        assert_eq!(solve_reader(input.as_bytes(), "example.txt").unwrap(), (13, 30));
+    }
+}
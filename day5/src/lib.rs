@@ -0,0 +1,764 @@
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use aoc_common::{lines, Error, Result, Rng, Solution};
+
+enum Intersection {
+    None, Equal, In, Out, Left, Right
+}
+
+trait Intersect {
+    fn intersect_with(&self, other: &Self) -> Intersection;
+}
+
+impl<T> Intersect for Range<T> 
+    where T: PartialOrd
+{
+    // 1..50 50..100
+    fn intersect_with(&self, other: &Self) -> Intersection {
+        if other.end == self.end && other.start == self.start {
+            Intersection::Equal
+        } else if other.start < self.start && self.end < other.end {
+            Intersection::In
+        } else if self.start <= other.start && other.end < self.end {
+            Intersection::Out
+        } else if other.start <= self.start && self.start < other.end {
+            Intersection::Left
+        } else if other.start <= self.end && self.end < other.end {
+            Intersection::Right
+        } else {
+            Intersection::None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SrcDst {
+    src: Range<i64>,
+    dst: Range<i64>,
+    // The input line of the rule
+    line: usize,
+}
+
+impl SrcDst {
+    fn new(src: Range<i64>, dst: Range<i64>, line: usize) -> Self {
+        Self { src, dst, line }
+    }
+
+    fn contains(&self, src: i64) -> bool {
+        return self.src.start <= src && src <= self.src.end;
+    }
+
+    fn is_valid(&self) -> bool {
+        return (self.src.end - self.src.start) == (self.dst.end - self.dst.start);
+    }
+
+    fn update_on_intersect(&mut self, other: &SrcDst) -> bool {
+        if !other.is_valid() {
+            return false;
+        }
+
+        let mut intersection_occured = false;
+
+        match self.src.intersect_with(&other.src) {
+            Intersection::None => (),
+            Intersection::Equal | Intersection::In => intersection_occured = true,
+            Intersection::Out => {
+                self.src.start = other.src.start;
+                self.src.end = other.src.end;
+                self.dst.start = other.dst.start;
+                self.dst.end = other.dst.end;
+                self.line = other.line;
+                intersection_occured = true;
+            },
+
+            Intersection::Left => {
+                self.dst.start = other.dst.start;
+                self.src.start = other.src.start;
+                intersection_occured = true;
+            },
+            Intersection::Right => {
+                self.src.end = other.src.end;
+                self.dst.end = other.dst.end;
+                intersection_occured = true;
+            },
+        }
+
+        // match self.dst.intersect_with(&other.dst) {
+        //     Intersection::None => (),
+        //     Intersection::Equal | Intersection::In => {
+        //         intersection_occured = true;
+        //     }
+        //     Intersection::Out => {
+        //         self.dst.start = other.dst.start;
+        //         self.dst.end = other.dst.end;
+        //         intersection_occured = true;
+        //     },
+
+        //     Intersection::Left => {
+        //         self.dst.start = other.dst.start;
+        //         intersection_occured = true;
+        //     },
+        //     Intersection::Right => {
+        //         self.dst.end = other.dst.end;
+        //         intersection_occured = true;
+        //     },
+        // }
+
+        return intersection_occured;
+    }
+
+    fn dst_of(&self, src: i64) -> Option<i64> {
+        if self.contains(src) {
+            let index = src - self.src.start;
+            return Some(self.dst.start + index);
+        } else {
+            return None;
+        }
+    }
+}
+
+// Seeds next to each other that went through the same rules so far. `range` is where they are
+// now (without its end) and `seed` is the seed at its start.
+#[derive(Debug, Clone)]
+struct Span {
+    range: Range<i64>,
+    seed: i64,
+}
+
+#[derive(Debug)]
+struct SrcDstList {
+    data: Vec<SrcDst>
+}
+
+impl SrcDstList {
+    fn new() -> Self {
+        Self { data: Vec::with_capacity(32) } 
+    }
+
+    fn add(&mut self, item: SrcDst) {
+        for srcdst in self.data.iter_mut() {
+            if srcdst.update_on_intersect(&item) {
+                return;
+            }
+        }
+        self.data.push(item)
+    }
+
+    fn dst_of(&self, src: i64) -> i64 {
+        for srcdst in self.data.iter() {
+            if let Some(dst) = srcdst.dst_of(src) {
+                return dst;
+            }
+        }
+        return src;
+    }
+
+    // The same with the line of the rule that matched
+    fn dst_and_line_of(&self, src: i64) -> (i64, Option<usize>) {
+        for srcdst in self.data.iter() {
+            if let Some(dst) = srcdst.dst_of(src) {
+                return (dst, Some(srcdst.line));
+            }
+        }
+        return (src, None);
+    }
+
+    // The same as `dst_of` for whole spans at once. A span is cut where a rule starts or ends,
+    // and a part that an earlier rule took isn't looked at by the later ones.
+    fn dst_of_spans(&self, spans: Vec<Span>) -> Vec<Span> {
+        let mut pending = spans;
+        let mut result = Vec::new();
+        for srcdst in self.data.iter() {
+            let mut rest = Vec::new();
+            for span in pending {
+                let start = span.range.start.max(srcdst.src.start);
+                let end = span.range.end.min(srcdst.src.end + 1);
+                if start >= end {
+                    rest.push(span);
+                    continue;
+                }
+
+                if span.range.start < start {
+                    rest.push(Span { range: span.range.start..start, seed: span.seed });
+                }
+                if end < span.range.end {
+                    rest.push(Span { range: end..span.range.end, seed: span.seed + end - span.range.start });
+                }
+                let shift = srcdst.dst.start - srcdst.src.start;
+                result.push(Span { range: start + shift..end + shift, seed: span.seed + start - span.range.start });
+            }
+            pending = rest;
+        }
+        result.extend(pending);
+        return result;
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seed_to_soil: SrcDstList,
+    soil_to_fertilizer: SrcDstList,
+    fertilizer_to_water: SrcDstList,
+    water_to_light: SrcDstList,
+    light_to_temperature: SrcDstList,
+    temperature_to_humidity: SrcDstList,
+    humidity_to_location: SrcDstList,
+    // The maps as they're written in the input and the lines with numbers before any label,
+    // only for `validate`
+    sections: Vec<Section>,
+    ignored_lines: Vec<usize>,
+}
+
+#[derive(Debug)]
+struct Section {
+    label: String,
+    line: usize,
+    // The line and [dst, src, size] of every rule
+    rules: Vec<(usize, [i64; 3])>,
+}
+
+// Where a seed is after one map, like soil 81, and the line and numbers of the rule that took it
+// there. Without a rule the value is the same as before the map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stage {
+    pub name: &'static str,
+    pub value: i64,
+    pub rule: Option<(usize, [i64; 3])>,
+}
+
+// Something in the input that's probably a mistake even though it parses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Lint {
+    fn at(line: usize, message: impl Into<String>) -> Self {
+        Self { line: Some(line), message: message.into() }
+    }
+
+    fn anywhere(message: impl Into<String>) -> Self {
+        Self { line: None, message: message.into() }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Almanac {
+    fn new() -> Self {
+        Self { 
+            seed_to_soil: SrcDstList::new(),
+            soil_to_fertilizer: SrcDstList::new(),
+            fertilizer_to_water: SrcDstList::new(),
+            water_to_light: SrcDstList::new(),
+            light_to_temperature: SrcDstList::new(),
+            temperature_to_humidity: SrcDstList::new(),
+            humidity_to_location: SrcDstList::new(),
+            sections: Vec::new(),
+            ignored_lines: Vec::new(),
+        }
+    }
+
+    // Everything the parser lets through but that likely isn't what was meant. `seeds` are
+    // checked as the pairs of start and amount of part 2.
+    pub fn validate(&self, seeds: &[i64]) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        for &line in &self.ignored_lines {
+            lints.push(Lint::at(line, "The numbers before the first label are ignored"));
+        }
+        if seeds.is_empty() {
+            lints.push(Lint::anywhere("There are no seeds"));
+        } else if seeds.len() % 2 == 1 {
+            lints.push(Lint::anywhere(format!("There are {} seeds, the last one has no amount to be a seed range", seeds.len())));
+        }
+
+        let mut furthest: Option<(usize, &Section)> = None;
+        for (i, section) in self.sections.iter().enumerate() {
+            let order = MAP_LABELS.iter().position(|label| *label == section.label).unwrap_or(0);
+            if let Some(first) = self.sections[..i].iter().find(|other| other.label == section.label) {
+                lints.push(Lint::at(section.line, format!("The {} is already on line {}, the rules of both are used", section.label, first.line)));
+                continue;
+            }
+            match furthest {
+                Some((furthest_order, furthest_section)) if order < furthest_order => {
+                    lints.push(Lint::at(section.line, format!("The {} comes after the {} on line {}, seeds go through it before",
+                        section.label, furthest_section.label, furthest_section.line)));
+                },
+                _ => furthest = Some((order, section)),
+            }
+        }
+
+        for label in MAP_LABELS {
+            let rules: Vec<(usize, [i64; 3])> = self.sections.iter()
+                .filter(|section| section.label == label)
+                .flat_map(|section| section.rules.iter().copied())
+                .collect();
+            if !self.sections.iter().any(|section| section.label == label) {
+                lints.push(Lint::anywhere(format!("The {label} is missing, nothing changes going through it")));
+            }
+
+            for (i, &(line, [dst, src, size])) in rules.iter().enumerate() {
+                if dst < 0 || src < 0 || size < 0 {
+                    lints.push(Lint::at(line, "The rule has a negative number"));
+                    continue;
+                }
+                if size == 0 {
+                    lints.push(Lint::at(line, "The rule has a length of 0 so it maps nothing"));
+                    continue;
+                }
+                let overlapping = rules[..i].iter()
+                    .find(|(_, [_, other_src, other_size])| *other_size > 0 && src < other_src + other_size && *other_src < src + size);
+                if let Some((other_line, _)) = overlapping {
+                    lints.push(Lint::at(line, format!("The sources {src}..{} overlap the rule on line {other_line}", src + size)));
+                }
+            }
+        }
+
+        lints.sort_by_key(|lint| lint.line.unwrap_or(0));
+        return lints;
+    }
+
+    pub fn location_of_seed(&self, seed: i64) -> i64 {
+        let mut location = seed;
+        location = self.seed_to_soil.dst_of(location);
+        location = self.soil_to_fertilizer.dst_of(location);
+        location = self.fertilizer_to_water.dst_of(location);
+        location = self.water_to_light.dst_of(location);
+        location = self.light_to_temperature.dst_of(location);
+        location = self.temperature_to_humidity.dst_of(location);
+        location = self.humidity_to_location.dst_of(location);
+        return location;
+    }
+
+    // The value of the seed after every map, see `Stage`
+    pub fn trace_seed(&self, seed: i64) -> Vec<Stage> {
+        let mut value = seed;
+        let mut stages = Vec::new();
+        for (map, label) in self.maps().into_iter().zip(MAP_LABELS) {
+            let (next, line) = map.dst_and_line_of(value);
+            value = next;
+            let rule = line.and_then(|line| self.rule_on_line(line));
+            let name = label.trim_end_matches(" map").split("-to-").last().unwrap_or(label);
+            stages.push(Stage { name, value, rule: line.zip(rule) });
+        }
+        return stages;
+    }
+
+    // The [dst, src, size] of the rule as it's written on `line`
+    fn rule_on_line(&self, line: usize) -> Option<[i64; 3]> {
+        self.sections.iter()
+            .flat_map(|section| section.rules.iter())
+            .find(|(rule_line, _)| *rule_line == line)
+            .map(|(_, rule)| *rule)
+    }
+
+    fn maps(&self) -> [&SrcDstList; 7] {
+        [
+            &self.seed_to_soil,
+            &self.soil_to_fertilizer,
+            &self.fertilizer_to_water,
+            &self.water_to_light,
+            &self.light_to_temperature,
+            &self.temperature_to_humidity,
+            &self.humidity_to_location,
+        ]
+    }
+
+    // Tries every seed of the range, this is the one that takes minutes on the real input
+    pub fn find_lowest_location_from_seed_range(&self, range: Range<i64>) -> (i64, i64) {
+        let mut result_seed = 0i64;
+        let mut result_location = i64::MAX;
+        for seed in range {
+            let location = self.location_of_seed(seed);
+            if location < result_location {
+                result_location = location;
+                result_seed = seed;
+            }
+        }
+
+        return (result_seed, result_location);
+    }
+
+    // The same for many ranges on `threads` threads. The ranges are cut into chunks that the
+    // threads take one by one, and `progress` is told how many seeds are done out of the total
+    // after every chunk. Ties go to the lowest seed so the answer doesn't depend on which thread
+    // finished first.
+    pub fn find_lowest_location_from_seed_ranges(
+        &self,
+        ranges: &[Range<i64>],
+        threads: usize,
+        progress: impl Fn(u64, u64) + Sync,
+    ) -> (i64, i64) {
+        self.find_lowest_location_in_chunks(ranges, SEED_CHUNK, threads, progress)
+    }
+
+    fn find_lowest_location_in_chunks(
+        &self,
+        ranges: &[Range<i64>],
+        chunk_size: i64,
+        threads: usize,
+        progress: impl Fn(u64, u64) + Sync,
+    ) -> (i64, i64) {
+        let mut chunks: Vec<Range<i64>> = Vec::new();
+        for range in ranges {
+            let mut start = range.start;
+            while start < range.end {
+                let end = range.end.min(start.saturating_add(chunk_size));
+                chunks.push(start..end);
+                start = end;
+            }
+        }
+        let total: u64 = chunks.iter().map(|chunk| (chunk.end - chunk.start) as u64).sum();
+
+        let next_chunk = AtomicUsize::new(0);
+        let done = AtomicU64::new(0);
+        let lowest = Mutex::new((i64::MAX, 0i64));
+        thread::scope(|scope| {
+            for _ in 0..threads.clamp(1, chunks.len().max(1)) {
+                scope.spawn(|| {
+                    let mut thread_lowest = (i64::MAX, 0i64);
+                    while let Some(chunk) = chunks.get(next_chunk.fetch_add(1, Ordering::Relaxed)) {
+                        let (seed, location) = self.find_lowest_location_from_seed_range(chunk.clone());
+                        thread_lowest = thread_lowest.min((location, seed));
+                        let amount = (chunk.end - chunk.start) as u64;
+                        progress(done.fetch_add(amount, Ordering::Relaxed) + amount, total);
+                    }
+                    let mut lowest = lowest.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                    *lowest = (*lowest).min(thread_lowest);
+                });
+            }
+        });
+
+        let (location, seed) = lowest.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+        return (if location == i64::MAX { 0 } else { seed }, location);
+    }
+
+    // The same by moving the whole range through the maps. The lowest location of a span is at
+    // its start since every seed in it moved by the same amount.
+    pub fn lowest_location_of_seed_range(&self, range: Range<i64>) -> (i64, i64) {
+        let mut spans = vec![Span { seed: range.start, range }];
+        for map in self.maps() {
+            spans = map.dst_of_spans(spans);
+        }
+
+        let mut result_seed = 0i64;
+        let mut result_location = i64::MAX;
+        for span in spans.iter().filter(|span| !span.range.is_empty()) {
+            if (span.range.start, span.seed) < (result_location, result_seed) {
+                result_location = span.range.start;
+                result_seed = span.seed;
+            }
+        }
+        return (result_seed, result_location);
+    }
+}
+
+// The seeds a thread tries at a time when trying every seed
+const SEED_CHUNK: i64 = 1 << 22;
+
+// Every map of the almanac in the order a seed goes through them
+const MAP_LABELS: [&str; 7] = [
+    "seed-to-soil map",
+    "soil-to-fertilizer map",
+    "fertilizer-to-water map",
+    "water-to-light map",
+    "light-to-temperature map",
+    "temperature-to-humidity map",
+    "humidity-to-location map",
+];
+
+// The numbers of an almanac as they're written in the input, for the property tests and `aoc gen`
+#[derive(Debug, Clone)]
+pub struct AlmanacNumbers {
+    // Pairs of start and amount, they're also the seeds of part 1
+    pub seed_ranges: Vec<(i64, i64)>,
+    // The [dst, src, size] rules of every map in the order of MAP_LABELS
+    pub maps: Vec<Vec<[i64; 3]>>,
+}
+
+impl AlmanacNumbers {
+    // Every number is below `max_value`. The rules of a map don't overlap like in the puzzle.
+    pub fn random(rng: &mut Rng, max_value: i64, max_rules: usize) -> Self {
+        let mut seed_ranges = Vec::new();
+        for _ in 0..rng.below(4) + 1 {
+            let start = rng.range(0..max_value);
+            seed_ranges.push((start, rng.range(1..max_value / 4 + 2)));
+        }
+
+        let mut maps = Vec::new();
+        for _ in MAP_LABELS {
+            // Cutting 0..max_value in pieces and using some of them as the sources
+            let mut cuts: Vec<i64> = (0..rng.below(2 * max_rules as u64 + 1)).map(|_| rng.range(0..max_value)).collect();
+            cuts.sort();
+            cuts.dedup();
+            let mut rules: Vec<[i64; 3]> = Vec::new();
+            for pair in cuts.windows(2) {
+                if rules.len() < max_rules && rng.chance(2, 3) {
+                    rules.push([rng.range(0..max_value), pair[0], pair[1] - pair[0]]);
+                }
+            }
+            rng.shuffle(&mut rules);
+            maps.push(rules);
+        }
+        Self { seed_ranges, maps }
+    }
+
+    pub fn to_input(&self) -> String {
+        let seeds: Vec<String> = self.seed_ranges.iter().map(|(start, amount)| format!("{start} {amount}")).collect();
+        let mut result = format!("seeds: {}\n", seeds.join(" "));
+        for (label, rules) in MAP_LABELS.iter().zip(&self.maps) {
+            result += &format!("\n{label}:\n");
+            for [dst, src, size] in rules {
+                result += &format!("{dst} {src} {size}\n");
+            }
+        }
+        return result;
+    }
+}
+
+pub struct Day5 {
+    pub seeds: Vec<i64>,
+    pub almanac: Almanac,
+}
+
+impl Day5 {
+    // The (seed, location) of the seed with the lowest location, seeds are single numbers
+    pub fn lowest_location_of_seeds(&self) -> (i64, i64) {
+        let mut part_1_result = i64::MAX;
+        let mut part_1_seed = 0;
+        for &seed in self.seeds.iter() {
+            let dst = self.almanac.location_of_seed(seed);
+            if dst < part_1_result {
+                part_1_seed = seed;
+                part_1_result = dst;
+            }
+        }
+        (part_1_seed, part_1_result)
+    }
+
+    // For part 2 the seeds are pairs of start and amount
+    pub fn seed_ranges(&self) -> Result<Vec<Range<i64>>> {
+        let mut ranges = Vec::new();
+        for chunk in self.seeds.chunks(2) {
+            let start = *chunk.first().ok_or_else(|| Error::validation("Failed to get the start of the seed range"))?;
+            let amount = *chunk.get(1).ok_or_else(|| Error::validation("Failed to get the amount of the seed range"))?;
+            ranges.push(start..start+amount);
+        }
+        Ok(ranges)
+    }
+
+    // The (seed, location) of the seed with the lowest location in all seed ranges
+    pub fn lowest_location_of_seed_ranges(&self) -> Result<(i64, i64)> {
+        let mut part_2_result = i64::MAX;
+        let mut part_2_seed = 0;
+        for range in self.seed_ranges()? {
+            let (seed, location) = self.almanac.lowest_location_of_seed_range(range);
+            if location < part_2_result {
+                part_2_result = location;
+                part_2_seed = seed;
+            }
+        }
+        Ok((part_2_seed, part_2_result))
+    }
+
+    // The same by trying every seed on `threads` threads, see
+    // `Almanac::find_lowest_location_from_seed_ranges`
+    pub fn lowest_location_of_seed_ranges_exhaustive(&self, threads: usize, progress: impl Fn(u64, u64) + Sync) -> Result<(i64, i64)> {
+        Ok(self.almanac.find_lowest_location_from_seed_ranges(&self.seed_ranges()?, threads, progress))
+    }
+}
+
+impl Solution for Day5 {
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self> {
+        let mut seeds = Vec::<i64>::new();
+        let mut almanac = Almanac::new();
+
+        let mut label: Option<&str> = None;
+
+        for line in lines(input) {
+            for content in line.text.split(":").map(|s| s.trim()).filter(|s| !s.is_empty()) {
+                if content == "seeds" || content.ends_with("map") {
+                    if content != "seeds" && !MAP_LABELS.contains(&content) {
+                        return Err(line.error("Unknown map", content));
+                    }
+                    if content != "seeds" {
+                        almanac.sections.push(Section { label: content.to_string(), line: line.number, rules: Vec::new() });
+                    }
+                    label = Some(content);
+                    continue;
+                }
+
+                let numbers: Vec<i64> = line.numbers(content)?;
+
+                if let Some(label) = label {
+                    if label == "seeds" {
+                        seeds.extend(numbers);
+                    } else if numbers.len() == 3 {
+                        let dst = numbers[0];
+                        let src = numbers[1];
+                        let size = numbers[2] - 1;
+                        let src_dst = SrcDst::new(src..src+size, dst..dst+size, line.number);
+                        if let Some(section) = almanac.sections.last_mut() {
+                            section.rules.push((line.number, [dst, src, numbers[2]]));
+                        }
+
+                        match label {
+                            "seed-to-soil map" => almanac.seed_to_soil.add(src_dst),
+                            "soil-to-fertilizer map" => almanac.soil_to_fertilizer.add(src_dst),
+                            "fertilizer-to-water map" => almanac.fertilizer_to_water.add(src_dst),
+                            "water-to-light map" => almanac.water_to_light.add(src_dst),
+                            "light-to-temperature map" => almanac.light_to_temperature.add(src_dst),
+                            "temperature-to-humidity map" => almanac.temperature_to_humidity.add(src_dst),
+                            "humidity-to-location map" => almanac.humidity_to_location.add(src_dst),
+                            _ => return Err(Error::validation(format!("Unknown map `{label}`"))),
+                        }
+                    } else {
+                        return Err(line.error("A map rule needs 3 numbers", content));
+                    }
+                } else if almanac.ignored_lines.last() != Some(&line.number) {
+                    almanac.ignored_lines.push(line.number);
+                }
+            }
+        }
+
+        Ok(Self { seeds, almanac })
+    }
+
+    fn part1(&self) -> Result<i64> {
+        Ok(self.lowest_location_of_seeds().1)
+    }
+
+    fn part2(&self) -> Result<i64> {
+        Ok(self.lowest_location_of_seed_ranges()?.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{check, shrink_vec};
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let puzzle = Day5::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 35);
+        assert_eq!(puzzle.part2().unwrap(), 46);
+    }
+
+    // Smaller almanacs with fewer or shorter seed ranges, or fewer or shorter rules in one map
+    fn shrink_almanac(almanac: &AlmanacNumbers) -> Vec<AlmanacNumbers> {
+        let shorter = |size: i64| if size > 1 { vec![1, size / 2, size - 1] } else { vec![] };
+        let mut result = Vec::new();
+        for seed_ranges in shrink_vec(&almanac.seed_ranges, |&(start, amount)| {
+            shorter(amount).into_iter().map(|amount| (start, amount)).collect()
+        }) {
+            // Without seeds there's no lowest location to compare
+            if !seed_ranges.is_empty() {
+                result.push(AlmanacNumbers { seed_ranges, ..almanac.clone() });
+            }
+        }
+        for (i, rules) in almanac.maps.iter().enumerate() {
+            for rules in shrink_vec(rules, |&[dst, src, size]| shorter(size).into_iter().map(|size| [dst, src, size]).collect()) {
+                let mut smaller = almanac.clone();
+                smaller.maps[i] = rules;
+                result.push(smaller);
+            }
+        }
+        return result;
+    }
+
+    #[test]
+    fn seed_ranges_match_trying_every_seed() {
+        check(
+            |rng| AlmanacNumbers::random(rng, 100, 4),
+            shrink_almanac,
+            |almanac| {
+                let puzzle = Day5::parse(&almanac.to_input()).unwrap();
+                let (seed, location) = puzzle.lowest_location_of_seed_ranges().unwrap();
+                let (_, expected) = puzzle.lowest_location_of_seed_ranges_exhaustive(2, |_, _| ()).unwrap();
+                location == expected && puzzle.almanac.location_of_seed(seed) == location
+            },
+        );
+    }
+
+    #[test]
+    fn chunks_on_threads_match_one_range_at_a_time() {
+        check(
+            |rng| AlmanacNumbers::random(rng, 100, 4),
+            shrink_almanac,
+            |almanac| {
+                let puzzle = Day5::parse(&almanac.to_input()).unwrap();
+                let ranges = puzzle.seed_ranges().unwrap();
+                let expected = ranges.iter()
+                    .map(|range| puzzle.almanac.find_lowest_location_from_seed_range(range.clone()))
+                    .map(|(seed, location)| (location, seed))
+                    .min()
+                    .map(|(location, seed)| (seed, location));
+                let seeds_done = AtomicU64::new(0);
+                let lowest = puzzle.almanac.find_lowest_location_in_chunks(&ranges, 7, 3, |done, total| {
+                    seeds_done.fetch_max(done, Ordering::Relaxed);
+                    assert!(done <= total);
+                });
+                let total: i64 = ranges.iter().map(|range| range.end - range.start).sum();
+                Some(lowest) == expected && seeds_done.into_inner() == total as u64
+            },
+        );
+    }
+
+    #[test]
+    fn lints() {
+        let puzzle = Day5::parse(include_str!("../example.txt")).unwrap();
+        assert!(puzzle.almanac.validate(&puzzle.seeds).is_empty());
+
+        let input = "1 2 3\nseeds: 79 14 55\n\nsoil-to-fertilizer map:\n0 15 37\n10 20 5\n5 100 0\n\n\
+            seed-to-soil map:\n50 98 2\n-1 3 4\n\nsoil-to-fertilizer map:\n1 40 10\n";
+        let puzzle = Day5::parse(input).unwrap();
+        let lints: Vec<String> = puzzle.almanac.validate(&puzzle.seeds).iter().map(|lint| lint.to_string()).collect();
+        assert_eq!(lints, [
+            "There are 3 seeds, the last one has no amount to be a seed range",
+            "The fertilizer-to-water map is missing, nothing changes going through it",
+            "The water-to-light map is missing, nothing changes going through it",
+            "The light-to-temperature map is missing, nothing changes going through it",
+            "The temperature-to-humidity map is missing, nothing changes going through it",
+            "The humidity-to-location map is missing, nothing changes going through it",
+            "line 1: The numbers before the first label are ignored",
+            "line 6: The sources 20..25 overlap the rule on line 5",
+            "line 7: The rule has a length of 0 so it maps nothing",
+            "line 9: The seed-to-soil map comes after the soil-to-fertilizer map on line 4, seeds go through it before",
+            "line 11: The rule has a negative number",
+            "line 13: The soil-to-fertilizer map is already on line 4, the rules of both are used",
+            "line 14: The sources 40..50 overlap the rule on line 5",
+        ]);
+    }
+
+    #[test]
+    fn trace() {
+        let puzzle = Day5::parse(include_str!("../example.txt")).unwrap();
+        let stages = puzzle.almanac.trace_seed(79);
+        let path: Vec<String> = stages.iter().map(|stage| format!("{} {}", stage.name, stage.value)).collect();
+        assert_eq!(path.join(" -> "), "soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 -> humidity 78 -> location 82");
+        let lines: Vec<Option<usize>> = stages.iter().map(|stage| stage.rule.map(|(line, _)| line)).collect();
+        assert_eq!(lines, [Some(5), None, None, Some(20), Some(25), None, Some(32)]);
+        assert_eq!(stages[0].rule, Some((5, [52, 50, 48])));
+
+        for &seed in &puzzle.seeds {
+            assert_eq!(puzzle.almanac.trace_seed(seed).last().unwrap().value, puzzle.almanac.location_of_seed(seed));
+        }
+    }
+}
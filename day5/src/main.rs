@@ -1,289 +1,96 @@
 use std::env;
-use std::fs;
-use std::ops::Range;
-
-enum Intersection {
-    None, Equal, In, Out, Left, Right
-}
-
-trait Intersect {
-    fn intersect_with(&self, other: &Self) -> Intersection;
-}
-
-impl<T> Intersect for Range<T> 
-    where T: PartialOrd
-{
-    // 1..50 50..100
-    fn intersect_with(&self, other: &Self) -> Intersection {
-        if other.end == self.end && other.start == self.start {
-            Intersection::Equal
-        } else if other.start < self.start && self.end < other.end {
-            Intersection::In
-        } else if self.start <= other.start && other.end < self.end {
-            Intersection::Out
-        } else if other.start <= self.start && self.start < other.end {
-            Intersection::Left
-        } else if other.start <= self.end && self.end < other.end {
-            Intersection::Right
-        } else {
-            Intersection::None
+use std::process::ExitCode;
+use std::thread;
+
+use aoc_common::{input_path, read_input, Error, Result, Solution};
+use day5::Day5;
+
+const USAGE: &str = "Usage: day5 <input> [--exhaustive [--threads <n>]] [--validate] [--trace <seed>]...";
+
+fn print_trace(puzzle: &Day5, seed: i64) {
+    let stages = puzzle.almanac.trace_seed(seed);
+    let path: Vec<String> = stages.iter().map(|stage| format!("{} {}", stage.name, stage.value)).collect();
+    println!("seed {seed} -> {}", path.join(" -> "));
+    for stage in stages {
+        match stage.rule {
+            Some((line, [dst, src, size])) => println!("    {} {} by the rule on line {line}: {dst} {src} {size}", stage.name, stage.value),
+            None => println!("    {} {} by no rule, the value stays the same", stage.name, stage.value),
         }
     }
 }
 
-#[derive(Debug)]
-struct SrcDst {
-    src: Range<i64>,
-    dst: Range<i64>,
-}
-
-impl SrcDst {
-    fn new(src: Range<i64>, dst: Range<i64>) -> Self {
-        Self { src, dst, }
-    }
-
-    fn contains(&self, src: i64) -> bool {
-        return self.src.start <= src && src <= self.src.end;
-    }
-
-    fn is_valid(&self) -> bool {
-        return (self.src.end - self.src.start) == (self.dst.end - self.dst.start);
-    }
-
-    fn update_on_intersect(&mut self, other: &SrcDst) -> bool {
-        if !other.is_valid() {
-            return false;
-        }
-
-        let mut intersection_occured = false;
-
-        match self.src.intersect_with(&other.src) {
-            Intersection::None => (),
-            Intersection::Equal | Intersection::In => intersection_occured = true,
-            Intersection::Out => {
-                self.src.start = other.src.start;
-                self.src.end = other.src.end;
-                self.dst.start = other.dst.start;
-                self.dst.end = other.dst.end;
-                intersection_occured = true;
-            },
-
-            Intersection::Left => {
-                self.dst.start = other.dst.start;
-                self.src.start = other.src.start;
-                intersection_occured = true;
-            },
-            Intersection::Right => {
-                self.src.end = other.src.end;
-                self.dst.end = other.dst.end;
-                intersection_occured = true;
-            },
-        }
-
-        // match self.dst.intersect_with(&other.dst) {
-        //     Intersection::None => (),
-        //     Intersection::Equal | Intersection::In => {
-        //         intersection_occured = true;
-        //     }
-        //     Intersection::Out => {
-        //         self.dst.start = other.dst.start;
-        //         self.dst.end = other.dst.end;
-        //         intersection_occured = true;
-        //     },
-
-        //     Intersection::Left => {
-        //         self.dst.start = other.dst.start;
-        //         intersection_occured = true;
-        //     },
-        //     Intersection::Right => {
-        //         self.dst.end = other.dst.end;
-        //         intersection_occured = true;
-        //     },
-        // }
-
-        return intersection_occured;
-    }
-
-    fn dst_of(&self, src: i64) -> Option<i64> {
-        if self.contains(src) {
-            let index = src - self.src.start;
-            return Some(self.dst.start + index);
-        } else {
-            return None;
-        }
-    }
-}
-
-#[derive(Debug)]
-struct SrcDstList {
-    data: Vec<SrcDst>
-}
-
-impl SrcDstList {
-    fn new() -> Self {
-        Self { data: Vec::with_capacity(32) } 
-    }
-
-    fn add(&mut self, item: SrcDst) {
-        for srcdst in self.data.iter_mut() {
-            if srcdst.update_on_intersect(&item) {
-                return;
-            }
-        }
-        self.data.push(item)
+fn solve_file(file_path: &str, exhaustive: bool, threads: usize, validate: bool, traces: &[i64]) -> Result<(i64, i64)> {
+    let puzzle = Day5::parse(&read_input(file_path)?)?;
+    for &seed in traces {
+        print_trace(&puzzle, seed);
     }
-
-    fn dst_of(&self, src: i64) -> i64 {
-        for srcdst in self.data.iter() {
-            if let Some(dst) = srcdst.dst_of(src) {
-                return dst;
-            }
+    if validate {
+        let lints = puzzle.almanac.validate(&puzzle.seeds);
+        for lint in &lints {
+            println!("{lint}");
         }
-        return src;
-    }
-}
-
-#[derive(Debug)]
-struct Almanac {
-    seed_to_soil: SrcDstList,
-    soil_to_fertilizer: SrcDstList,
-    fertilizer_to_water: SrcDstList,
-    water_to_light: SrcDstList,
-    light_to_temperature: SrcDstList,
-    temperature_to_humidity: SrcDstList,
-    humidity_to_location: SrcDstList,
-}
-
-impl Almanac {
-    fn new() -> Self {
-        Self { 
-            seed_to_soil: SrcDstList::new(),
-            soil_to_fertilizer: SrcDstList::new(),
-            fertilizer_to_water: SrcDstList::new(),
-            water_to_light: SrcDstList::new(),
-            light_to_temperature: SrcDstList::new(),
-            temperature_to_humidity: SrcDstList::new(),
-            humidity_to_location: SrcDstList::new(),
+        if !lints.is_empty() {
+            return Err(Error::validation(format!("Found {} problems in the almanac", lints.len())));
         }
     }
+    let (part_1_seed, part_1_result) = puzzle.lowest_location_of_seeds();
+    // Trying every seed is the old way, it's kept to check the ranges against
+    let (part_2_seed, part_2_result) = if exhaustive {
+        let result = puzzle.lowest_location_of_seed_ranges_exhaustive(threads, |done, total| {
+            eprint!("\rTried {done} of {total} seeds ({:.1}%)", done as f64 * 100.0 / total as f64);
+        })?;
+        eprintln!();
+        result
+    } else {
+        puzzle.lowest_location_of_seed_ranges()?
+    };
 
-    fn location_of_seed(&mut self, seed: i64) -> i64 {
-        let mut location = seed;
-        location = self.seed_to_soil.dst_of(location);
-        location = self.soil_to_fertilizer.dst_of(location);
-        location = self.fertilizer_to_water.dst_of(location);
-        location = self.water_to_light.dst_of(location);
-        location = self.light_to_temperature.dst_of(location);
-        location = self.temperature_to_humidity.dst_of(location);
-        location = self.humidity_to_location.dst_of(location);
-        return location;
-    }
-
-    fn find_lowest_location_from_seed_range(&mut self, range: Range<i64>) -> (i64, i64) {
-        let mut result_seed = 0i64;
-        let mut result_location = i64::MAX;
-        for seed in range {
-            let location = self.location_of_seed(seed);
-            if location < result_location {
-                result_location = location;
-                result_seed = seed;
-            }
-        }
-
-        return (result_seed, result_location);
-    }
+    println!("Seed of part 1: {part_1_seed}");
+    println!("Seed of part 2: {part_2_seed}");
+    Ok((part_1_result, part_2_result))
 }
 
-fn solve_file(file_path: &str) -> Result<(i64, i64), String> {
-    let file_content = fs::read_to_string(file_path).map_err(|err| err.to_string())?;
-    
-    let mut seeds = Vec::<i64>::new();
-    let mut almanac = Almanac::new();
-
-    let file_content = file_content.lines()
-        .filter(|s| !s.is_empty())
-        .map(|s| s.split(":"))
-        .flatten()
-        .filter(|s| !s.is_empty())
-        .map(|s| s.trim())
-        .collect::<Vec<&str>>();
-    let mut label: Option<&str> = None;
-
-    // parsing
-    for content in file_content {
-        if content == "seeds" || content.ends_with("map") {
-            label = Some(content);
-        } else {
-            let mut numbers: Vec<i64> = Vec::new();
-            for num in content.trim().split_whitespace() {
-                numbers.push(num.parse().map_err(|_| "Failed to parse number")?);
-            }
-
-            if let Some(label) = label {
-                if label == "seeds" {
-                    seeds.extend(numbers);
-                } else if numbers.len() == 3 {
-                    let dst = numbers[0];
-                    let src = numbers[1];
-                    let size = numbers[2] - 1;
-                    let src_dst = SrcDst::new(src..src+size, dst..dst+size);
-
-                    match label {
-                        "seed-to-soil map" => almanac.seed_to_soil.add(src_dst),
-                        "soil-to-fertilizer map" => almanac.soil_to_fertilizer.add(src_dst),
-                        "fertilizer-to-water map" => almanac.fertilizer_to_water.add(src_dst),
-                        "water-to-light map" => almanac.water_to_light.add(src_dst),
-                        "light-to-temperature map" => almanac.light_to_temperature.add(src_dst),
-                        "temperature-to-humidity map" => almanac.temperature_to_humidity.add(src_dst),
-                        "humidity-to-location map" => almanac.humidity_to_location.add(src_dst),
-                        _ => return Err("WTF".to_string()),
-                    }
-                } else {
-                    return Err("Unparseable string".to_string());
+fn run(args: &[String]) -> Result<(i64, i64)> {
+    let file_path = input_path(args).map_err(|e| Error::validation(format!("{e}\n{USAGE}")))?;
+    let mut exhaustive = false;
+    let mut validate = false;
+    let mut traces: Vec<i64> = Vec::new();
+    // Every core by default
+    let mut threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--exhaustive" => exhaustive = true,
+            "--validate" => validate = true,
+            "--trace" => {
+                let seed = options.next().and_then(|value| value.parse().ok())
+                    .ok_or_else(|| Error::validation("--trace expects a seed"))?;
+                traces.push(seed);
+            },
+            "--threads" => {
+                match options.next().map(|value| value.parse::<usize>()) {
+                    Some(Ok(value)) if value > 0 => threads = value,
+                    _ => return Err(Error::validation("--threads expects a number above 0")),
                 }
-            }
-        }
-    }
-
-    // part 1
-    let mut part_1_result = i64::MAX;
-    let mut part_1_seed = 0;
-    for seed in seeds.iter() {
-        let seed = seed.clone();
-        let dst = almanac.location_of_seed(seed);
-        if dst < part_1_result {
-            part_1_seed = seed;
-            part_1_result = dst;
-        }
-    }
-
-    // part 2
-    let mut part_2_result = i64::MAX;
-    let mut part_2_seed = 0;
-    for chunk in seeds.chunks(2) {
-        let start = chunk.get(0).ok_or("Failed to get the start of the seed range")?.clone();
-        let amount = chunk.get(1).ok_or("Failed to get the start of the seed range")?.clone();
-        println!("{start} {amount}");
-        let (seed, location) = almanac.find_lowest_location_from_seed_range(start..start+amount);
-        if location < part_2_result {
-            part_2_result = location;
-            part_2_seed = seed;
+            },
+            _ => return Err(Error::validation(format!("Unknown option {option}\n{USAGE}"))),
         }
     }
-
-    println!("Seed of part 1: {part_1_seed}");
-    println!("Seed of part 2: {part_2_seed}");
-    Ok((part_1_result, part_2_result))
+    solve_file(file_path, exhaustive, threads, validate, &traces)
 }
 
-fn main() {
-    let file_path = env::args().skip(1).next().expect("Please provide an input file path");
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
 
-    match solve_file(&file_path) {
+    match run(&args) {
         Ok((part_1, part_2)) => {
             println!("Result of part 1: {part_1}");
             println!("Result of part 2: {part_2}");
+            ExitCode::SUCCESS
+        },
+        Err(error_message) => {
+            eprintln!("ERROR: {}", error_message);
+            ExitCode::FAILURE
         },
-        Err(error_message) => eprintln!("ERROR: {}", error_message),
     }
 }
@@ -0,0 +1,155 @@
+use aoc_common::{lines, Error, Line, Result, Rng, Solution};
+
+// How many ways to hold the button beat the record, by trying every hold time
+pub fn count_winning_ways(race_time: u64, record_distance: u64) -> u64 {
+    let mut winning_count = 0;
+    for speed in 0..=race_time {
+        let remaining_time = race_time - speed;
+        let achieved_distance = remaining_time * speed;
+        // print!("Remaining time = {remaining_time}, Achieved Distance = {achieved_distance}");
+        if achieved_distance > record_distance {
+            // print!(" Winning");
+            winning_count += 1;
+        }
+        // println!();
+    }
+    return winning_count;
+}
+
+// The same without trying every hold time. Holding for h goes h * (time - h), which is more than
+// the record between the two roots of h^2 - time * h + record = 0, so only the first winning
+// hold time is needed and the rest is symmetric around time / 2.
+pub fn count_winning_ways_fast(race_time: u64, record_distance: u64) -> u64 {
+    let distance = |hold: u64| hold as u128 * (race_time - hold) as u128;
+    let record = record_distance as u128;
+    let half = race_time / 2;
+    if distance(half) <= record {
+        return 0;
+    }
+
+    // The square root is only a guess because of the rounding, the loops fix it up
+    let discriminant = (race_time as u128 * race_time as u128).saturating_sub(4 * record);
+    let mut first = ((race_time as u128 - discriminant.isqrt().min(race_time as u128)) / 2) as u64;
+    while distance(first) <= record {
+        first += 1;
+    }
+    while first > 0 && distance(first - 1) > record {
+        first -= 1;
+    }
+    return race_time - 2 * first + 1;
+}
+
+// Random races for the property tests and `aoc gen`. Every record can be beaten like in the puzzle
+// unless `beatable` is false.
+pub fn random_races(rng: &mut Rng, amount: usize, max_time: u64, beatable: bool) -> Vec<(u64, u64)> {
+    let mut races = Vec::new();
+    for _ in 0..amount {
+        let time = rng.below(max_time + 1);
+        let best = (time / 2) * (time - time / 2);
+        let record = if beatable { rng.below(best.max(1)) } else { rng.below(best + 2) };
+        races.push((time, record));
+    }
+    return races;
+}
+
+pub fn races_to_input(races: &[(u64, u64)]) -> String {
+    let times: Vec<String> = races.iter().map(|(time, _)| format!("{time:>4}")).collect();
+    let distances: Vec<String> = races.iter().map(|(_, record)| format!("{record:>4}")).collect();
+    format!("Time:    {}\nDistance:{}\n", times.join(" "), distances.join(" "))
+}
+
+pub struct Day6 {
+    // (time, record distance) of every race for part 1
+    races: Vec<(u64, u64)>,
+    // The single race of part 2 where the spaces between the digits are ignored
+    race: (u64, u64),
+}
+
+impl Solution for Day6 {
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        let lines: Vec<Line> = lines(input).collect();
+        if lines.len() < 2 {
+            return Err(Error::validation("Expecting a line of times and a line of distances"));
+        }
+
+        let times: Vec<u64> = lines[0].text
+            .split_whitespace()
+            .filter_map(|s| s.parse().ok())
+            .collect();
+        let distances: Vec<u64> = lines[1].text
+            .split_whitespace()
+            .filter_map(|s| s.parse().ok())
+            .collect();
+
+        if times.len() != distances.len() {
+            return Err(Error::validation(format!("There are {} times but {} distances", times.len(), distances.len())));
+        }
+
+        let mut race_time = 0;
+        let mut target_distance = 0;
+        for line in lines {
+            let numbers = line.text.split(":")
+                .nth(1).ok_or_else(|| line.error("Expecting a label before `:`", line.text))?;
+            let number = numbers
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join("");
+            let number: u64 = number.parse().map_err(|_| line.error("Failed to parse number", numbers.trim()))?;
+            race_time = if race_time == 0 { number } else { race_time };
+            target_distance = if race_time != 0 { number } else { 0 };
+        }
+
+        Ok(Self { races: times.into_iter().zip(distances).collect(), race: (race_time, target_distance) })
+    }
+
+    fn part1(&self) -> Result<u64> {
+        let mut result_of_part_1 = 1;
+        for &(race_time, record_distance) in &self.races {
+            // println!("Race time: {race_time}, Target: {record_distance}");
+            result_of_part_1 *= count_winning_ways_fast(race_time, record_distance);
+        }
+        return Ok(result_of_part_1);
+    }
+
+    fn part2(&self) -> Result<u64> {
+        let (race_time, target_distance) = self.race;
+        Ok(count_winning_ways_fast(race_time, target_distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{check, shrink_u64, shrink_vec};
+
+    use super::*;
+
+    #[test]
+    fn example() {
+        let puzzle = Day6::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 288);
+        assert_eq!(puzzle.part2().unwrap(), 71503);
+    }
+
+    #[test]
+    fn fast_matches_trying_every_hold_time() {
+        check(
+            |rng| random_races(rng, 5, 200, false),
+            |races| shrink_vec(races, |&(time, record)| {
+                let mut result: Vec<(u64, u64)> = shrink_u64(time).into_iter().map(|time| (time, record)).collect();
+                result.extend(shrink_u64(record).into_iter().map(|record| (time, record)));
+                result
+            }),
+            |races| races.iter().all(|&(time, record)| count_winning_ways_fast(time, record) == count_winning_ways(time, record)),
+        );
+    }
+
+    #[test]
+    fn generated_input_parses() {
+        let races = random_races(&mut Rng::new(6), 4, 100, true);
+        let puzzle = Day6::parse(&races_to_input(&races)).unwrap();
+        assert_eq!(puzzle.races, races);
+    }
+}
@@ -0,0 +1,282 @@
+use std::io::BufRead;
+
+use aoc_common::{lines, read_lines, Error, Line, Result, Rng, Solution};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Card {
+    Unknown,
+    Joker,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandKind {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAkind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+pub struct Hand {
+    pub cards: [Card; 5],
+    pub kind: HandKind,
+    pub bid: u64,
+}
+
+impl Hand {
+    // With `jokers` the J cards are jokers like in part 2, otherwise they're jacks
+    pub fn new(cards: &str, bid: u64, jokers: bool) -> Result<Self> {
+        if cards.len() != 5 {
+            return Err(Error::validation(format!("Allowed card for a hand is only 5, `{cards}` has {}", cards.len())));
+        }
+
+        let mut result = Self { bid, kind: HandKind::HighCard, cards: [Card::Unknown, Card::Unknown, Card::Unknown, Card::Unknown, Card::Unknown] };
+        let cards: Vec<char> = cards.chars().collect();
+
+        let mut joker_count = 0;
+        for (i, card) in cards.iter().enumerate() {
+            let current_card = match card {
+                'A' => Card::Ace,
+                'K' => Card::King,
+                'Q' => Card::Queen,
+                'T' => Card::Ten,
+                '9' => Card::Nine,
+                '8' => Card::Eight,
+                '7' => Card::Seven,
+                '6' => Card::Six,
+                '5' => Card::Five,
+                '4' => Card::Four,
+                '3' => Card::Three,
+                '2' => Card::Two,
+                'J' if jokers => Card::Joker,
+                'J' => Card::Jack,
+                _ => Card::Unknown,
+            };
+
+            if current_card == Card::Joker {
+                joker_count += 1;
+            } else {
+                let mut same_card_counter = 0;
+                for same_card in &result.cards[..i] {
+                    if current_card == *same_card {
+                        same_card_counter += 1;
+                    }
+                }
+
+                result.kind = match same_card_counter {
+                    0 => result.kind,
+                    1 => match result.kind {
+                        HandKind::HighCard => HandKind::OnePair,
+                        HandKind::OnePair => HandKind::TwoPair,
+                        HandKind::ThreeOfAkind => HandKind::FullHouse,
+                        _ => {
+                            return Err(Error::validation(format!("Unexpected kind transition from {:?} with {same_card_counter} matching cards", result.kind)))
+                        }
+                    },
+                    2 => match result.kind {
+                        HandKind::OnePair => HandKind::ThreeOfAkind,
+                        HandKind::TwoPair => HandKind::FullHouse,
+                        _ => {
+                            return Err(Error::validation(format!("Unexpected kind transition from {:?} with {same_card_counter} matching cards", result.kind)))
+                        },
+                    },
+                    3 => match result.kind {
+                        HandKind::ThreeOfAkind => HandKind::FourOfAKind,
+                        _ => {
+                            return Err(Error::validation(format!("Unexpected kind transition from {:?} with {same_card_counter} matching cards", result.kind)))
+                        }
+                    },
+                    4 => match result.kind {
+                        HandKind::FourOfAKind => HandKind::FiveOfAKind,
+                        _ => {
+                            return Err(Error::validation(format!("Unexpected kind transition from {:?} with {same_card_counter} matching cards", result.kind)))
+                        }
+                    }
+                    _ => return Err(Error::validation("Unreachable error"))
+                };
+            }
+            result.cards[i] = current_card;
+        }
+
+        result.kind = match joker_count {
+            0 => result.kind,
+            1 => match result.kind {
+                HandKind::HighCard => HandKind::OnePair,
+                HandKind::OnePair => HandKind::ThreeOfAkind,
+                HandKind::ThreeOfAkind => HandKind::FourOfAKind,
+                HandKind::FourOfAKind => HandKind::FiveOfAKind,
+                HandKind::TwoPair => HandKind::FullHouse,
+                _ => {
+                    return Err(Error::validation(format!("Unexpected kind transition from {:?} with {joker_count} jokers", result.kind)))
+                },
+            },
+            2 => match result.kind {
+                HandKind::HighCard => HandKind::ThreeOfAkind,
+                HandKind::OnePair => HandKind::FourOfAKind,
+                HandKind::ThreeOfAkind => HandKind::FiveOfAKind,
+                _ => {
+                    return Err(Error::validation(format!("Unexpected kind transition from {:?} with {joker_count} jokers", result.kind)))
+                },
+            },
+            3 => match result.kind {
+                HandKind::HighCard => HandKind::FourOfAKind,
+                HandKind::OnePair => HandKind::FiveOfAKind,
+                _ => {
+                    return Err(Error::validation(format!("Unexpected kind transition from {:?} with {joker_count} jokers", result.kind)))
+                }
+            },
+            4 => match result.kind {
+                HandKind::HighCard => HandKind::FiveOfAKind,
+                _ => {
+                    return Err(Error::validation(format!("Unexpected kind transition from {:?} with {joker_count} jokers", result.kind)))
+                }
+            }
+            5 => HandKind::FiveOfAKind,
+            _ => return Err(Error::validation("Unreachable error"))
+        };
+
+        Ok(result)
+    }
+}
+
+pub fn cmp_hands(a: &Hand, b: &Hand) -> std::cmp::Ordering {
+    match a.kind.cmp(&b.kind) {
+        std::cmp::Ordering::Equal => {
+            for i in 0..a.cards.len() {
+                match a.cards[i].cmp(&b.cards[i]) {
+                    std::cmp::Ordering::Equal => continue,
+                    std::cmp::Ordering::Greater => return std::cmp::Ordering::Greater,
+                    std::cmp::Ordering::Less => return std::cmp::Ordering::Less,
+                }
+            }
+            return std::cmp::Ordering::Equal;
+        },
+        std::cmp::Ordering::Less => std::cmp::Ordering::Less,
+        std::cmp::Ordering::Greater => std::cmp::Ordering::Greater,
+    }
+}
+
+// Ranks the hands from the weakest and adds up every bid times its rank
+fn total_winnings(mut hands: Vec<Hand>) -> u64 {
+    hands.sort_by(cmp_hands);
+    let mut result = 0;
+    for (i, hand) in hands.iter().enumerate() {
+        result += hand.bid * (i + 1) as u64;
+    }
+    return result;
+}
+
+// Random hands for `aoc gen`. A hand is drawn from 1 to 5 labels so every kind shows up.
+pub fn random_hands(rng: &mut Rng, amount: usize) -> Vec<(String, u64)> {
+    const LABELS: [char; 13] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
+    let mut hands = Vec::new();
+    for _ in 0..amount {
+        let labels: Vec<char> = (0..rng.below(5) + 1).map(|_| *rng.choose(&LABELS)).collect();
+        let cards: String = (0..5).map(|_| *rng.choose(&labels)).collect();
+        hands.push((cards, rng.below(1000) + 1));
+    }
+    hands
+}
+
+pub fn hands_to_input(hands: &[(String, u64)]) -> String {
+    hands.iter().map(|(cards, bid)| format!("{cards} {bid}\n")).collect()
+}
+
+// The cards and the bid of one line
+fn parse_line<'a>(line: &Line<'a>) -> Result<(&'a str, u64)> {
+    let mut line_it = line.text.split_whitespace();
+    let cards = line_it.next().ok_or_else(|| line.error("Failed to parse cards", line.text))?;
+    let bid = line_it.next().ok_or_else(|| line.error("Failed to parse the bid", line.text))?;
+    Ok((cards, line.parse(bid)?))
+}
+
+// Both parts without keeping the input. Ranking needs every hand at once, so the hands of both
+// parts are kept but not their lines.
+pub fn solve_reader(reader: impl BufRead, path: &str) -> Result<(u64, u64)> {
+    let mut hands = Vec::new();
+    let mut hands_with_jokers = Vec::new();
+    read_lines(reader, path, |line| {
+        let (cards, bid) = parse_line(&line)?;
+        hands.push(Hand::new(cards, bid, false)?);
+        hands_with_jokers.push(Hand::new(cards, bid, true)?);
+        Ok(())
+    })?;
+    Ok((total_winnings(hands), total_winnings(hands_with_jokers)))
+}
+
+pub struct Day7 {
+    // The cards and the bid of every line
+    hands: Vec<(String, u64)>,
+}
+
+impl Day7 {
+    fn winnings(&self, jokers: bool) -> Result<u64> {
+        let mut hands: Vec<Hand> = Vec::with_capacity(self.hands.len());
+        for (cards, bid) in &self.hands {
+            hands.push(Hand::new(cards, *bid, jokers)?);
+        }
+        Ok(total_winnings(hands))
+    }
+}
+
+impl Solution for Day7 {
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        let mut hands = Vec::new();
+        for line in lines(input) {
+            let (cards, bid) = parse_line(&line)?;
+            hands.push((cards.to_string(), bid));
+        }
+        Ok(Self { hands })
+    }
+
+    fn part1(&self) -> Result<u64> {
+        self.winnings(false)
+    }
+
+    fn part2(&self) -> Result<u64> {
+        self.winnings(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let puzzle = Day7::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 6440);
+        assert_eq!(puzzle.part2().unwrap(), 5905);
+    }
+
+    #[test]
+    fn reader_matches_whole_input() {
+        let input = include_str!("../example.txt");
+        assert_eq!(solve_reader(input.as_bytes(), "example.txt").unwrap(), (6440, 5905));
+    }
+
+    #[test]
+    fn bad_bid() {
+        let error = Day7::parse("32T3K 765\nT55J5 68x\n").err().unwrap();
+        assert_eq!(error.to_string(), "line 2, column 7: Failed to parse number `68x`");
+        assert_eq!(error.span(), Some(6..9));
+    }
+}
@@ -0,0 +1,879 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::{lines, Error, Line, Result, Rng, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Left,
+    Right,
+}
+
+// The nodes under dense ids in the order they're defined, so walking is indexing a Vec
+// instead of hashing names. Every target is checked to exist when parsing.
+#[derive(Debug)]
+pub struct Network {
+    pub names: Vec<String>,
+    pub ids: HashMap<String, u32>,
+    // The left and right targets of every node
+    pub nodes: Vec<(u32, u32)>,
+}
+
+impl Network {
+    pub fn id(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: u32) -> &str {
+        &self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn target(&self, id: u32, instruction: Instruction) -> u32 {
+        let (left, right) = self.nodes[id as usize];
+        match instruction {
+            Instruction::Left => left,
+            Instruction::Right => right,
+        }
+    }
+
+    // Lines like `AAA = (BBB, CCC)`. Every node has to be defined once and every target has to
+    // be a defined node.
+    fn parse<'a>(lines: impl Iterator<Item = Line<'a>>) -> Result<Self> {
+        let mut names: Vec<String> = Vec::new();
+        let mut ids: HashMap<String, u32> = HashMap::new();
+        let mut definitions: Vec<(Line, &str, &str)> = Vec::new();
+        let mut defined_on: Vec<usize> = Vec::new();
+
+        for line in lines {
+            let (name, targets) = line.text.split_once('=')
+                .ok_or_else(|| line.error("Failed to parse the node", line.text))?;
+            let name = name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(line.error("Invalid node name", name));
+            }
+            let targets = targets.trim();
+            let inner = targets.strip_prefix('(').and_then(|targets| targets.strip_suffix(')'))
+                .ok_or_else(|| line.error(format!("Expected the targets of node {name} in parentheses"), targets))?;
+            let (left, right) = inner.split_once(',')
+                .ok_or_else(|| line.error(format!("Failed to parse the targets of node {name}"), targets))?;
+            let (left, right) = (left.trim(), right.trim());
+            for target in [left, right] {
+                if target.is_empty() || target.contains(char::is_whitespace) {
+                    return Err(line.error(format!("Invalid target of node {name}"), target));
+                }
+            }
+
+            if let Some(&id) = ids.get(name) {
+                return Err(line.error(format!("Node {name} is already defined on line {}", defined_on[id as usize]), name));
+            }
+            ids.insert(name.to_string(), names.len() as u32);
+            names.push(name.to_string());
+            defined_on.push(line.number);
+            definitions.push((line, left, right));
+        }
+
+        let mut nodes: Vec<(u32, u32)> = Vec::with_capacity(definitions.len());
+        for (line, left, right) in definitions {
+            let id = |target: &str| ids.get(target).copied()
+                .ok_or_else(|| line.error("Target is not a defined node", target));
+            nodes.push((id(left)?, id(right)?));
+        }
+        Ok(Self { names, ids, nodes })
+    }
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// The x in 0..n with a * x = 1 (mod n), `a` and `n` have no common divisor
+fn inverse(a: u128, n: u128) -> u128 {
+    let (mut old_r, mut r) = (a as i128, n as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+    old_s.rem_euclid(n as i128) as u128
+}
+
+// The numbers that are `a` modulo `m` and `b` modulo `n` at once, as a remainder and a modulus.
+// None when there are none, an error when the modulus doesn't fit in a u64.
+fn crt((a, m): (u128, u128), (b, n): (u128, u128)) -> Result<Option<(u128, u128)>> {
+    let g = gcd(m, n);
+    if a % g != b % g {
+        return Ok(None);
+    }
+    let modulus = m / g * n;
+    if modulus > u64::MAX as u128 {
+        return Err(Error::no_solution("The walkers only meet after more steps than fit in a u64"));
+    }
+    let n_g = n / g;
+    let k = ((b + n - a % n) % n / g) % n_g * inverse(m / g % n_g, n_g) % n_g;
+    Ok(Some(((a + m * k) % modulus, modulus)))
+}
+
+// A random network for `aoc gen` shaped like the puzzle's. Every start walks a path of its own
+// that takes a whole number of rounds of the instructions (1 to `max_rounds`) to reach its end
+// node, and the end node leads where the start does, so each start loops with that period.
+// The first start is AAA and its end is ZZZ. Returns the input and the period of every start.
+// There are only 676 names for the starts and the ends and 16224 for the other nodes, so the
+// paths have to fit in those.
+pub fn random_network(rng: &mut Rng, instructions: usize, starts: usize, max_rounds: usize) -> (String, Vec<usize>) {
+    let instructions: Vec<char> = (0..instructions.max(1)).map(|_| if rng.chance(1, 2) { 'L' } else { 'R' }).collect();
+    let mut used: HashSet<String> = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut name = |rng: &mut Rng, last: Option<char>| loop {
+        let mut name: String = (0..2).map(|_| char::from(b'A' + rng.below(26) as u8)).collect();
+        // Nodes in the middle of a path end with neither A nor Z
+        name.push(last.unwrap_or_else(|| char::from(b'B' + rng.below(24) as u8)));
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    let mut nodes: Vec<(String, String, String)> = Vec::new();
+    let mut periods = Vec::new();
+    for start in 0..starts.max(1) {
+        let period = instructions.len() * (rng.below(max_rounds.max(1) as u64) as usize + 1);
+        let mut path: Vec<String> = Vec::with_capacity(period + 1);
+        path.push(if start == 0 { "AAA".to_string() } else { name(rng, Some('A')) });
+        for _ in 1..period {
+            path.push(name(rng, None));
+        }
+        path.push(if start == 0 { "ZZZ".to_string() } else { name(rng, Some('Z')) });
+
+        let first = nodes.len();
+        for i in 0..period {
+            // The way the walk doesn't take can go anywhere on the path
+            let other = path[rng.below(period as u64 + 1) as usize].clone();
+            let next = path[i + 1].clone();
+            let (left, right) = if instructions[i % instructions.len()] == 'L' { (next, other) } else { (other, next) };
+            nodes.push((path[i].clone(), left, right));
+        }
+        let (_, left, right) = nodes[first].clone();
+        nodes.push((path[period].clone(), left, right));
+        periods.push(period);
+    }
+
+    rng.shuffle(&mut nodes);
+    let mut result: String = instructions.iter().collect();
+    result += "\n\n";
+    for (node, left, right) in nodes {
+        result += &format!("{node} = ({left}, {right})\n");
+    }
+    return (result, periods);
+}
+
+// Which nodes a walk starts from or ends on. From the command line a plain `AAA` or `AAA,BBB`
+// are the exact names, `*Z` is every name ending with Z, and any other `*` or `?` makes a
+// pattern where `*` is any amount of characters and `?` is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Nodes {
+    Names(Vec<String>),
+    Suffix(String),
+    Pattern(String),
+}
+
+impl Nodes {
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Names(names) => names.iter().any(|other| other == name),
+            Self::Suffix(suffix) => name.ends_with(suffix.as_str()),
+            Self::Pattern(pattern) => {
+                let pattern: Vec<char> = pattern.chars().collect();
+                let name: Vec<char> = name.chars().collect();
+                glob(&pattern, &name)
+            },
+        }
+    }
+}
+
+fn glob(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skipped| glob(rest, &name[skipped..])),
+        Some((&expected, rest)) => match name.split_first() {
+            Some((&found, name)) => (expected == '?' || expected == found) && glob(rest, name),
+            None => false,
+        },
+    }
+}
+
+impl FromStr for Nodes {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(Error::validation("Expected node names or a pattern"));
+        }
+        if let Some(suffix) = text.strip_prefix('*').filter(|suffix| !suffix.contains(['*', '?'])) {
+            return Ok(Self::Suffix(suffix.to_string()));
+        }
+        if text.contains(['*', '?']) {
+            return Ok(Self::Pattern(text.to_string()));
+        }
+        Ok(Self::Names(text.split(',').map(|name| name.trim().to_string()).collect()))
+    }
+}
+
+impl fmt::Display for Nodes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Names(names) => write!(f, "{}", names.join(",")),
+            Self::Suffix(suffix) => write!(f, "*{suffix}"),
+            Self::Pattern(pattern) => write!(f, "{pattern}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    // One walker from the only start until it's on an end node
+    Single,
+    // A walker from every start, until all of them are on end nodes at the same time
+    Multi,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Walk {
+    pub mode: Mode,
+    pub starts: Nodes,
+    pub ends: Nodes,
+    // The most steps a walker may take, walks that never get to an end node are found
+    // without it too
+    pub max_steps: Option<usize>,
+}
+
+impl Walk {
+    // From AAA to ZZZ
+    pub fn part1() -> Self {
+        Self { mode: Mode::Single, starts: Nodes::Names(vec!["AAA".to_string()]), ends: Nodes::Names(vec!["ZZZ".to_string()]), max_steps: None }
+    }
+
+    // From every node ending with A to nodes ending with Z
+    pub fn part2() -> Self {
+        Self { mode: Mode::Multi, starts: Nodes::Suffix("A".to_string()), ends: Nodes::Suffix("Z".to_string()), max_steps: None }
+    }
+}
+
+pub struct Day8 {
+    pub instructions: Vec<Instruction>,
+    pub network: Network,
+}
+
+impl Day8 {
+    pub fn next(&self, current: u32, i: usize) -> u32 {
+        self.network.target(current, self.instructions[i % self.instructions.len()])
+    }
+
+    // The ids of the matching nodes in the order they're defined
+    pub fn select(&self, nodes: &Nodes) -> Vec<u32> {
+        (0..self.network.len() as u32).filter(|&id| nodes.matches(self.network.name(id))).collect()
+    }
+
+    // Whether each node matches, by id
+    pub fn flags(&self, nodes: &Nodes) -> Vec<bool> {
+        self.network.names.iter().map(|name| nodes.matches(name)).collect()
+    }
+
+    pub fn steps(&self, walk: &Walk) -> Result<usize> {
+        let starts = self.select(&walk.starts);
+        if starts.is_empty() {
+            return Err(Error::no_solution(format!("There is no start node matching {}", walk.starts)));
+        }
+        let ends = self.flags(&walk.ends);
+        if !ends.contains(&true) {
+            return Err(Error::no_solution(format!("There is no end node matching {}", walk.ends)));
+        }
+
+        match walk.mode {
+            Mode::Single => {
+                let [start] = starts[..] else {
+                    return Err(Error::validation(format!("A single walker needs one start but {} matches {} nodes", walk.starts, starts.len())));
+                };
+                let mut guard = Guard::new(self, start, walk.max_steps);
+                let mut current = start;
+                let mut i = 0;
+                while !ends[current as usize] {
+                    guard.check(current, i)?;
+                    current = self.next(current, i);
+                    i += 1;
+                }
+                Ok(i)
+            },
+            Mode::Multi => self.together(&starts, &ends, walk.max_steps),
+        }
+    }
+
+    // The first step every walker is on an end node at once. Before the longest tail the steps
+    // are tried one end of the first walker at a time. After it every walker is on an end node
+    // exactly at the steps that have the same remainder as one of its end steps in the cycle,
+    // so the answer is the smallest step from there on that fits a remainder of every walker.
+    fn together(&self, starts: &[u32], ends: &[bool], max_steps: Option<usize>) -> Result<usize> {
+        let jumps = Jumps::new(self);
+        let cycles: Vec<Cycle> = starts.iter().map(|&start| jumps.cycle(start, ends)).collect();
+
+        let tail = cycles.iter().map(|cycle| cycle.tail).max().unwrap_or(0);
+        let mut result: Option<u64> = None;
+        let mut step = cycles[0].next_end(0);
+        while let Some(current) = step.filter(|&step| step < tail) {
+            if cycles.iter().all(|cycle| cycle.next_end(current) == Some(current)) {
+                result = Some(current);
+                break;
+            }
+            step = cycles[0].next_end(current + 1);
+        }
+
+        if result.is_none() {
+            for (&start, cycle) in starts.iter().zip(&cycles) {
+                if cycle.ends.last().is_none_or(|&end| end < cycle.tail) {
+                    let name = self.network.name(start);
+                    let again = cycle.ends.last().map_or(String::new(), |end| format!(" again after step {end}"));
+                    return Err(Error::no_solution(format!(
+                        "start {name} never reaches an end node{again} (cycle of length {} entered at step {})", cycle.length, cycle.tail
+                    )));
+                }
+            }
+
+            let mut remainders: Vec<(u128, u128)> = vec![(0, 1)];
+            for cycle in &cycles {
+                let mut next = Vec::new();
+                for &remainder in &remainders {
+                    for end in cycle.ends.iter().filter(|&&end| end >= cycle.tail) {
+                        if let Some(combined) = crt(remainder, ((end % cycle.length) as u128, cycle.length as u128))? {
+                            if !next.contains(&combined) {
+                                next.push(combined);
+                            }
+                        }
+                    }
+                }
+                remainders = next;
+            }
+            let tail = tail as u128;
+            result = remainders.iter()
+                .map(|&(remainder, modulus)| if remainder >= tail { remainder } else { remainder + (tail - remainder).div_ceil(modulus) * modulus })
+                .min()
+                .map(|step| u64::try_from(step).map_err(|_| Error::no_solution("The walkers only meet after more steps than fit in a u64")))
+                .transpose()?;
+        }
+
+        let result = result.ok_or_else(|| Error::no_solution("The walkers are never on end nodes at the same time"))?;
+        let result = usize::try_from(result).map_err(|_| Error::no_solution("The walkers only meet after more steps than fit in a usize"))?;
+        if let Some(max_steps) = max_steps.filter(|&max_steps| result > max_steps) {
+            return Err(Error::no_solution(format!("The walkers went over the budget of {max_steps} steps")));
+        }
+        Ok(result)
+    }
+}
+
+// Stops a single walker that went over its budget, or that's back on a node at the start of a
+// pass of the instructions, because then it goes around that cycle forever without getting to
+// an end node. Any cycle has whole passes in it, so looking at the start of the passes is enough.
+// Walkers together don't need it, their cycles come from `Jumps::cycle`.
+struct Guard<'a> {
+    puzzle: &'a Day8,
+    start: u32,
+    max_steps: Option<usize>,
+    // The step the walk was first on every node at the start of a pass
+    seen: Vec<Option<usize>>,
+}
+
+impl<'a> Guard<'a> {
+    fn new(puzzle: &'a Day8, start: u32, max_steps: Option<usize>) -> Self {
+        Self { puzzle, start, max_steps, seen: Vec::new() }
+    }
+
+    // With the node the walk is on after `steps` steps, before taking the next one. The walk
+    // hasn't been on an end node yet, it would have stopped there.
+    fn check(&mut self, node: u32, steps: usize) -> Result<()> {
+        let name = self.puzzle.network.name(self.start);
+        if let Some(max_steps) = self.max_steps.filter(|&max_steps| steps >= max_steps) {
+            return Err(Error::no_solution(format!("start {name} went over the budget of {max_steps} steps")));
+        }
+
+        if !steps.is_multiple_of(self.puzzle.instructions.len()) {
+            return Ok(());
+        }
+        // Only walks that go on for a whole pass pay for this
+        if self.seen.is_empty() {
+            self.seen = vec![None; self.puzzle.network.len()];
+        }
+        if let Some(first) = self.seen[node as usize] {
+            return Err(Error::no_solution(format!(
+                "start {name} never reaches an end node (cycle of length {} entered at step {first})", steps - first
+            )));
+        }
+        self.seen[node as usize] = Some(steps);
+        Ok(())
+    }
+}
+
+// Where every node ends up after 2^k whole passes of the instructions, for every k, so a walk of
+// any length is a few lookups plus less than one pass of single steps
+pub struct Jumps<'a> {
+    puzzle: &'a Day8,
+    levels: Vec<Vec<u32>>,
+}
+
+impl<'a> Jumps<'a> {
+    pub fn new(puzzle: &'a Day8) -> Self {
+        let pass: Vec<u32> = (0..puzzle.network.len() as u32)
+            .map(|node| (0..puzzle.instructions.len()).fold(node, |current, i| puzzle.next(current, i)))
+            .collect();
+        let mut levels = vec![pass];
+        while levels.len() < u64::BITS as usize {
+            let last = &levels[levels.len() - 1];
+            let next = last.iter().map(|&node| last[node as usize]).collect();
+            levels.push(next);
+        }
+        Self { puzzle, levels }
+    }
+
+    // The node after one pass from `node`
+    pub fn pass(&self, node: u32) -> u32 {
+        self.levels[0][node as usize]
+    }
+
+    pub fn passes(&self, mut node: u32, passes: u64) -> u32 {
+        for (k, level) in self.levels.iter().enumerate() {
+            if passes >> k & 1 == 1 {
+                node = level[node as usize];
+            }
+        }
+        node
+    }
+
+    // Where a walk from `start` is after `steps` steps
+    pub fn position_after(&self, start: u32, steps: u64) -> u32 {
+        let length = self.puzzle.instructions.len() as u64;
+        let mut current = self.passes(start, steps / length);
+        for i in 0..(steps % length) as usize {
+            current = self.puzzle.next(current, i);
+        }
+        current
+    }
+
+    // The walk from `start` seen one pass at a time. The node at the start of a pass decides the
+    // whole pass, so once one comes back everything after it repeats.
+    pub fn cycle(&self, start: u32, ends: &[bool]) -> Cycle {
+        let length = self.puzzle.instructions.len() as u64;
+        let mut first_seen: Vec<Option<u64>> = vec![None; self.puzzle.network.len()];
+        let mut end_steps = Vec::new();
+        let mut current = start;
+        let mut pass = 0;
+        while first_seen[current as usize].is_none() {
+            first_seen[current as usize] = Some(pass);
+            for i in 0..length as usize {
+                if ends[current as usize] {
+                    end_steps.push(pass * length + i as u64);
+                }
+                current = self.puzzle.next(current, i);
+            }
+            pass += 1;
+        }
+        let tail_passes = first_seen[current as usize].unwrap_or(0);
+        Cycle { tail: tail_passes * length, length: (pass - tail_passes) * length, ends: end_steps }
+    }
+}
+
+// The steps a walk is on an end node. After `tail` steps the walk repeats every `length` steps,
+// `ends` has every such step before `tail + length`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub tail: u64,
+    pub length: u64,
+    pub ends: Vec<u64>,
+}
+
+impl Cycle {
+    // The first step from `step` on where the walk is on an end node. None when it never is
+    // again, or only past what u64 holds.
+    pub fn next_end(&self, step: u64) -> Option<u64> {
+        if let Some(&end) = self.ends.iter().find(|&&end| end >= step) {
+            return Some(end);
+        }
+        let repeating: Vec<u64> = self.ends.iter().filter(|&&end| end >= self.tail).map(|end| end - self.tail).collect();
+        let first = *repeating.first()?;
+        let since = step - self.tail;
+        let (rounds, offset) = (since / self.length, since % self.length);
+        match repeating.iter().find(|&&end| end >= offset) {
+            Some(&end) => self.tail.checked_add(rounds.checked_mul(self.length)?)?.checked_add(end),
+            None => self.tail.checked_add((rounds + 1).checked_mul(self.length)?)?.checked_add(first),
+        }
+    }
+}
+
+// What `Day8::analyze` finds out about a network
+pub struct Analysis {
+    // The strongly connected components of the graph with both targets of every node, the
+    // biggest first and then in the order they're defined
+    pub components: Vec<Vec<u32>>,
+    // For every start, the end nodes its walk gets to and the ones it could get to in the graph
+    // if it could choose the way at every node
+    pub reached: Vec<(u32, Vec<u32>, Vec<u32>)>,
+    // The nodes no walk from a start ever gets to
+    pub unvisited: Vec<u32>,
+}
+
+impl Day8 {
+    // Tarjan's algorithm without recursion, the real input has long paths
+    pub fn components(&self) -> Vec<Vec<u32>> {
+        const UNSEEN: usize = usize::MAX;
+        let size = self.network.len();
+        let mut index = vec![UNSEEN; size];
+        let mut low = vec![0; size];
+        let mut on_stack = vec![false; size];
+        let mut stack: Vec<u32> = Vec::new();
+        let mut counter = 0;
+        let mut result: Vec<Vec<u32>> = Vec::new();
+
+        for root in 0..size as u32 {
+            if index[root as usize] != UNSEEN {
+                continue;
+            }
+            // The node and which of its two targets is next
+            let mut work: Vec<(u32, usize)> = vec![(root, 0)];
+            while let Some((node, edge)) = work.pop() {
+                let v = node as usize;
+                if edge == 0 {
+                    index[v] = counter;
+                    low[v] = counter;
+                    counter += 1;
+                    stack.push(node);
+                    on_stack[v] = true;
+                }
+                if edge < 2 {
+                    let (left, right) = self.network.nodes[v];
+                    let target = if edge == 0 { left } else { right };
+                    work.push((node, edge + 1));
+                    if index[target as usize] == UNSEEN {
+                        work.push((target, 0));
+                    } else if on_stack[target as usize] {
+                        low[v] = low[v].min(index[target as usize]);
+                    }
+                    continue;
+                }
+
+                if low[v] == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member as usize] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    result.push(component);
+                }
+                if let Some(&(parent, _)) = work.last() {
+                    low[parent as usize] = low[parent as usize].min(low[v]);
+                }
+            }
+        }
+        result.sort_by(|a, b| b.len().cmp(&a.len()).then(a[0].cmp(&b[0])));
+        result
+    }
+
+    // Every node the walk from `start` is ever on. It's over once a pass starts on a node a
+    // pass started on before.
+    pub fn visited(&self, start: u32) -> Vec<bool> {
+        let mut visited = vec![false; self.network.len()];
+        let mut pass_started = vec![false; self.network.len()];
+        let mut current = start;
+        while !pass_started[current as usize] {
+            pass_started[current as usize] = true;
+            for i in 0..self.instructions.len() {
+                visited[current as usize] = true;
+                current = self.next(current, i);
+            }
+        }
+        visited[current as usize] = true;
+        visited
+    }
+
+    // Every node with a path from `start`, whatever the instructions say
+    pub fn reachable(&self, start: u32) -> Vec<bool> {
+        let mut reachable = vec![false; self.network.len()];
+        reachable[start as usize] = true;
+        let mut queue = vec![start];
+        while let Some(node) = queue.pop() {
+            let (left, right) = self.network.nodes[node as usize];
+            for target in [left, right] {
+                if !reachable[target as usize] {
+                    reachable[target as usize] = true;
+                    queue.push(target);
+                }
+            }
+        }
+        reachable
+    }
+
+    pub fn analyze(&self, walk: &Walk) -> Analysis {
+        let ends = self.flags(&walk.ends);
+        let matching = |flags: &[bool]| (0..self.network.len() as u32)
+            .filter(|&id| flags[id as usize] && ends[id as usize])
+            .collect::<Vec<u32>>();
+
+        let mut ever_visited = vec![false; self.network.len()];
+        let mut reached = Vec::new();
+        for start in self.select(&walk.starts) {
+            let visited = self.visited(start);
+            for (ever, now) in ever_visited.iter_mut().zip(&visited) {
+                *ever |= *now;
+            }
+            reached.push((start, matching(&visited), matching(&self.reachable(start))));
+        }
+        let unvisited = (0..self.network.len() as u32).filter(|&id| !ever_visited[id as usize]).collect();
+        Analysis { components: self.components(), reached, unvisited }
+    }
+
+    // The network as a Graphviz graph, with the starts in green and the ends in red
+    pub fn to_dot(&self, walk: &Walk) -> String {
+        let mut result = String::from("digraph network {\n    node [style=filled, fillcolor=white];\n");
+        for (id, name) in self.network.names.iter().enumerate() {
+            if walk.starts.matches(name) {
+                result += &format!("    \"{name}\" [fillcolor=palegreen];\n");
+            } else if walk.ends.matches(name) {
+                result += &format!("    \"{name}\" [fillcolor=salmon];\n");
+            }
+            let (left, right) = self.network.nodes[id];
+            if left == right {
+                result += &format!("    \"{name}\" -> \"{}\" [label=\"L,R\"];\n", self.network.name(left));
+            } else {
+                result += &format!("    \"{name}\" -> \"{}\" [label=L];\n", self.network.name(left));
+                result += &format!("    \"{name}\" -> \"{}\" [label=R];\n", self.network.name(right));
+            }
+        }
+        result += "}\n";
+        result
+    }
+}
+
+impl Solution for Day8 {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        let mut lines = lines(input).filter(|line| !line.text.trim().is_empty());
+        let instruction_line = lines.next()
+            .ok_or_else(|| Error::validation("Failed to parse the instruction of the Map"))?;
+        let text = instruction_line.text.trim();
+        let mut instructions: Vec<Instruction> = Vec::with_capacity(text.len());
+        for (i, instruction) in text.char_indices() {
+            instructions.push(match instruction {
+                'L' => Instruction::Left,
+                'R' => Instruction::Right,
+                _ => return Err(instruction_line.error("Unknown instruction", &text[i..i + instruction.len_utf8()])),
+            });
+        }
+
+        if instructions.is_empty() {
+            return Err(instruction_line.error("There are no instructions", instruction_line.text));
+        }
+
+        let network = Network::parse(lines)?;
+        Ok(Self { instructions, network })
+    }
+
+    fn part1(&self) -> Result<usize> {
+        self.steps(&Walk::part1())
+    }
+
+    fn part2(&self) -> Result<usize> {
+        self.steps(&Walk::part2())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lcm(a: usize, b: usize) -> usize {
+        a / gcd(a as u128, b as u128) as usize * b
+    }
+
+    #[test]
+    fn example() {
+        let puzzle = Day8::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(puzzle.part1().unwrap(), 2);
+    }
+
+    #[test]
+    fn example2() {
+        let puzzle = Day8::parse(include_str!("../example2.txt")).unwrap();
+        assert_eq!(puzzle.part2().unwrap(), 6);
+    }
+
+    #[test]
+    fn random_network_periods() {
+        let mut rng = Rng::new(8);
+        for _ in 0..20 {
+            let (input, periods) = random_network(&mut rng, 7, 3, 4);
+            let puzzle = Day8::parse(&input).unwrap();
+            assert_eq!(puzzle.part1().unwrap(), periods[0]);
+            assert_eq!(puzzle.part2().unwrap(), periods.iter().fold(1, |result, &period| lcm(result, period)));
+        }
+    }
+
+    #[test]
+    fn jumps_match_walking() {
+        let mut rng = Rng::new(44);
+        for _ in 0..20 {
+            let (input, _) = random_network(&mut rng, 5, 3, 3);
+            let puzzle = Day8::parse(&input).unwrap();
+            let jumps = Jumps::new(&puzzle);
+            let ends = puzzle.flags(&Walk::part2().ends);
+            for start in puzzle.select(&Walk::part2().starts) {
+                let cycle = jumps.cycle(start, &ends);
+                let mut walk = vec![start];
+                for i in 0..200 {
+                    walk.push(puzzle.next(walk[i], i));
+                }
+                for (steps, &node) in walk.iter().enumerate() {
+                    assert_eq!(jumps.position_after(start, steps as u64), node);
+                    let expected = (steps..walk.len()).find(|&step| ends[walk[step] as usize]);
+                    if let Some(expected) = expected {
+                        assert_eq!(cycle.next_end(steps as u64), Some(expected as u64));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn walkers_together_match_walking() {
+        let mut rng = Rng::new(45);
+        for _ in 0..500 {
+            let (input, _) = random_network(&mut rng, 3, 3, 3);
+            let puzzle = Day8::parse(&input).unwrap();
+            let names: Vec<String> = puzzle.network.names.iter().filter(|_| rng.chance(1, 2)).cloned().collect();
+            if names.is_empty() {
+                continue;
+            }
+            let walk = Walk { mode: Mode::Multi, starts: Walk::part2().starts, ends: Nodes::Names(names), max_steps: None };
+            let ends = puzzle.flags(&walk.ends);
+            let mut current = puzzle.select(&walk.starts);
+            let expected = (0..5000).find(|&step| {
+                let all_on_ends = current.iter().all(|&node| ends[node as usize]);
+                current = current.iter().map(|&node| puzzle.next(node, step)).collect();
+                all_on_ends
+            });
+            match expected {
+                Some(expected) => assert_eq!(puzzle.steps(&walk).unwrap(), expected),
+                None => assert!(puzzle.steps(&walk).is_err()),
+            }
+        }
+    }
+
+    #[test]
+    fn jumps_far_ahead() {
+        let puzzle = Day8::parse(include_str!("../example2.txt")).unwrap();
+        let jumps = Jumps::new(&puzzle);
+        let ends = puzzle.flags(&Walk::part2().ends);
+        let (ghost_1, ghost_2) = (puzzle.network.id("11A").unwrap(), puzzle.network.id("22A").unwrap());
+        let far = 1_000_000_000_000_000_000;
+        assert_eq!(puzzle.network.name(jumps.position_after(ghost_1, far)), "11Z");
+        assert_eq!(puzzle.network.name(jumps.position_after(ghost_2, far)), "22B");
+        assert_eq!(jumps.cycle(ghost_1, &ends).next_end(far + 1), Some(far + 2));
+        assert_eq!(jumps.cycle(ghost_2, &ends).next_end(far), Some(far + 2));
+        // XXX only leads to itself
+        assert_eq!(jumps.cycle(puzzle.network.id("XXX").unwrap(), &ends).next_end(0), None);
+    }
+
+    #[test]
+    fn custom_walks() {
+        let puzzle = Day8::parse(include_str!("../example2.txt")).unwrap();
+        let walk = |mode, starts: &str, ends: &str| puzzle.steps(&Walk { mode, starts: starts.parse().unwrap(), ends: ends.parse().unwrap(), max_steps: None });
+        assert_eq!(walk(Mode::Single, "11A", "*Z").unwrap(), 2);
+        assert_eq!(walk(Mode::Single, "22A", "22Z").unwrap(), 3);
+        assert_eq!(walk(Mode::Single, "22A", "2?C").unwrap(), 2);
+        assert_eq!(walk(Mode::Multi, "11A,22A", "*Z").unwrap(), 6);
+        // Both are on a B node after 1 step, and on 11B and 22Z after 3
+        assert_eq!(walk(Mode::Multi, "11A,22A", "*B").unwrap(), 1);
+        assert_eq!(walk(Mode::Multi, "11A,22A", "22Z,11B").unwrap(), 3);
+        // 11A is on 11Z at every even step and 22A on 22C at 2, 5, 8 and so on
+        assert_eq!(walk(Mode::Multi, "11A,22A", "11Z,22C").unwrap(), 2);
+        assert_eq!(walk(Mode::Multi, "11A,22A", "11B,22C").unwrap(), 5);
+        assert_eq!(walk(Mode::Single, "*A", "*Z").unwrap_err().to_string(), "A single walker needs one start but *A matches 2 nodes");
+        assert_eq!(walk(Mode::Single, "AAA", "*Z").unwrap_err().to_string(), "No solution: There is no start node matching AAA");
+    }
+
+    #[test]
+    fn walks_that_never_end() {
+        let puzzle = Day8::parse(include_str!("../example2.txt")).unwrap();
+        let walk = |mode, starts: &str, ends: &str, max_steps| {
+            let walk = Walk { mode, starts: starts.parse().unwrap(), ends: ends.parse().unwrap(), max_steps };
+            puzzle.steps(&walk).unwrap_err().to_string()
+        };
+        assert_eq!(walk(Mode::Single, "11A", "22Z", None), "No solution: start 11A never reaches an end node (cycle of length 2 entered at step 2)");
+        assert_eq!(walk(Mode::Multi, "*A", "11Z", None), "No solution: start 22A never reaches an end node (cycle of length 6 entered at step 2)");
+        assert_eq!(walk(Mode::Single, "22A", "22Z", Some(2)), "No solution: start 22A went over the budget of 2 steps");
+        // Only XXX leads to XXX
+        assert_eq!(walk(Mode::Single, "XXX", "*Z", None), "No solution: start XXX never reaches an end node (cycle of length 2 entered at step 0)");
+        assert_eq!(walk(Mode::Multi, "11A,22A", "11Z,22A", None), "No solution: start 22A never reaches an end node again after step 0 (cycle of length 6 entered at step 2)");
+
+        // BBB is only on the way into the loop on CCC
+        let puzzle = Day8::parse("LR\n\nAAA = (BBB, BBB)\nBBB = (CCC, CCC)\nCCC = (CCC, CCC)\n").unwrap();
+        let walk = |mode| puzzle.steps(&Walk { mode, starts: "AAA".parse().unwrap(), ends: "BBB".parse().unwrap(), max_steps: None });
+        assert_eq!(walk(Mode::Single).unwrap(), 1);
+        assert_eq!(walk(Mode::Multi).unwrap(), 1);
+        let walk = |mode| puzzle.steps(&Walk { mode, starts: "AAA,CCC".parse().unwrap(), ends: "BBB".parse().unwrap(), max_steps: None });
+        assert_eq!(walk(Mode::Multi).unwrap_err().to_string(), "No solution: start AAA never reaches an end node again after step 1 (cycle of length 2 entered at step 2)");
+    }
+
+    #[test]
+    fn node_patterns() {
+        assert_eq!("*Z".parse::<Nodes>().unwrap(), Nodes::Suffix("Z".to_string()));
+        assert_eq!("AAA, BBB".parse::<Nodes>().unwrap(), Nodes::Names(vec!["AAA".to_string(), "BBB".to_string()]));
+        let pattern: Nodes = "1*?Z".parse().unwrap();
+        assert!(pattern.matches("11Z") && pattern.matches("1ABCZ"));
+        assert!(!pattern.matches("1Z") && !pattern.matches("21Z"));
+    }
+
+    #[test]
+    fn analysis() {
+        let puzzle = Day8::parse(include_str!("../example2.txt")).unwrap();
+        let names = |ids: &[u32]| ids.iter().map(|&id| puzzle.network.name(id)).collect::<Vec<&str>>().join(" ");
+        let analysis = puzzle.analyze(&Walk::part2());
+
+        let components: Vec<String> = analysis.components.iter().map(|component| names(component)).collect();
+        assert_eq!(components, ["22B 22C 22Z", "11B 11Z", "11A", "22A", "XXX"]);
+        let reached: Vec<(&str, String, String)> = analysis.reached.iter()
+            .map(|(start, walk, graph)| (puzzle.network.name(*start), names(walk), names(graph)))
+            .collect();
+        assert_eq!(reached, [("11A", "11Z".to_string(), "11Z".to_string()), ("22A", "22Z".to_string(), "22Z".to_string())]);
+        assert_eq!(names(&analysis.unvisited), "XXX");
+    }
+
+    #[test]
+    fn dot() {
+        let puzzle = Day8::parse(include_str!("../example.txt")).unwrap();
+        let dot = puzzle.to_dot(&Walk::part1());
+        assert!(dot.starts_with("digraph network {\n"));
+        assert!(dot.contains("    \"AAA\" [fillcolor=palegreen];\n    \"AAA\" -> \"BBB\" [label=L];\n    \"AAA\" -> \"CCC\" [label=R];\n"));
+        assert!(dot.contains("    \"ZZZ\" [fillcolor=salmon];\n    \"ZZZ\" -> \"ZZZ\" [label=\"L,R\"];\n"));
+    }
+
+    #[test]
+    fn invalid_networks() {
+        let error = |input: &str| Day8::parse(input).err().unwrap().to_string();
+        assert_eq!(error("LR\n\nAAA = (BBB, ZZZ)\nBBB = (ZZZ, ZZZ)\n"), "line 3, column 13: Target is not a defined node `ZZZ`");
+        assert_eq!(error("LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)\n"), "line 4, column 1: Node AAA is already defined on line 3 `AAA`");
+        assert_eq!(error("LR\n\nAAA = (AAA AAA)\n"), "line 3, column 7: Failed to parse the targets of node AAA `(AAA AAA)`");
+        assert_eq!(error("LR\n\nAAA = AAA, AAA\n"), "line 3, column 7: Expected the targets of node AAA in parentheses `AAA, AAA`");
+        assert_eq!(error("LR\n\nAAA (AAA, AAA)\n"), "line 3, column 1: Failed to parse the node `AAA (AAA, AAA)`");
+        assert_eq!(error("LXR\n\nAAA = (AAA, AAA)\n"), "line 1, column 2: Unknown instruction `X`");
+    }
+}
@@ -1,88 +1,146 @@
-use std::fs;
 use std::env;
-use std::collections::HashMap;
-
-#[derive(Debug)]
-struct Node {
-    left: String,
-    right: String,
-}
+use std::fs;
+use std::process::ExitCode;
 
-fn solve_file(file_path: &str) -> Result<usize, String> {
-    let file_content = fs::read_to_string(file_path).map_err(|e| e.to_string())?;
-    let mut lines = file_content.trim().lines();
-    let instructions: Vec<char> = lines.next().ok_or("Failed to parse the instruction of the Map")?
-        .trim()
-        .chars()
-        .collect();
+use aoc_common::{input_path, read_input, Error, Result, Solution};
+use day8::{Day8, Jumps, Mode, Nodes, Walk};
 
-    lines.next();
+const USAGE: &str = "Usage: day8 <input|-> [--start <nodes>] [--end <nodes>] [--mode single|multi] [--max-steps <n>] [--at <steps>] [--dot <file>] [--analyze]
+Nodes are names like AAA or AAA,BBB, a suffix like *Z or a pattern like 1?A";
 
-    let mut maps: HashMap<String, Node> = HashMap::new();
-    let mut starts: Vec<String> = Vec::new();
+struct Options {
+    // Only when --start, --end or --mode is given
+    walk: Option<Walk>,
+    // For both parts and the walk above
+    max_steps: Option<usize>,
+    at: Option<u64>,
+    dot: Option<String>,
+    analyze: bool,
+}
 
-    for line in lines {
-        let mut line_it = line.trim().split("=");
-        let name = line_it.next().ok_or("Failed to parse the name of a node")?.trim().to_string();
-        let mut targets = line_it.next()
-            .ok_or(format!("Failed to parse the targets of node {name}"))?
-            .trim()
-            .trim_matches(|c| c == '(' || c == ')')
-            .split(",");
-        let left = targets.next().ok_or("Failed to parse the left target of a node")?.trim().to_string();
-        let right = targets.next().ok_or("Failed to parse the right target of a node")?.trim().to_string();
+fn run(args: &[String]) -> Result<(Day8, Options)> {
+    let file_path = input_path(args).map_err(|e| Error::validation(format!("{e}\n{USAGE}")))?;
 
-        if name.ends_with('A') {
-            starts.push(name.clone());
+    let mut starts: Option<Nodes> = None;
+    let mut ends: Option<Nodes> = None;
+    let mut mode: Option<Mode> = None;
+    let mut max_steps: Option<usize> = None;
+    let mut at: Option<u64> = None;
+    let mut dot: Option<String> = None;
+    let mut analyze = false;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        if option == "--analyze" {
+            analyze = true;
+            continue;
+        }
+        let value = options.next().ok_or_else(|| Error::validation(format!("Missing value for {option}\n{USAGE}")))?;
+        match option.as_str() {
+            "--start" => starts = Some(value.parse()?),
+            "--end" => ends = Some(value.parse()?),
+            "--mode" => mode = Some(match value.as_str() {
+                "single" => Mode::Single,
+                "multi" => Mode::Multi,
+                _ => return Err(Error::validation(format!("Unknown mode {value}, expected single or multi"))),
+            }),
+            "--max-steps" => max_steps = Some(value.parse().map_err(|_| Error::validation("--max-steps expects a number of steps"))?),
+            "--at" => at = Some(value.parse().map_err(|_| Error::validation("--at expects a number of steps"))?),
+            "--dot" => dot = Some(value.clone()),
+            _ => return Err(Error::validation(format!("Unknown option {option}\n{USAGE}"))),
         }
-        maps.insert(name, Node { left, right });
     }
 
-    let mut every_required_steps: Vec<Vec<usize>> = Vec::new();
-    for start in starts.iter() {
-        let mut current = start;
-        let mut current_required_steps: Vec<usize> = Vec::new();
+    // Without a mode one start name means one walker, like part 1, and anything else means
+    // walking from all of them together, like part 2
+    let walk = if starts.is_some() || ends.is_some() || mode.is_some() {
+        let mode = mode.unwrap_or(match &starts {
+            Some(Nodes::Names(names)) if names.len() == 1 => Mode::Single,
+            Some(_) => Mode::Multi,
+            None => Mode::Single,
+        });
+        let default = if mode == Mode::Single { Walk::part1() } else { Walk::part2() };
+        Some(Walk { mode, starts: starts.unwrap_or(default.starts), ends: ends.unwrap_or(default.ends), max_steps })
+    } else {
+        None
+    };
 
-        let mut last = 0;
-        let mut i = 0;
-        'calculate_step: loop {
-            match instructions[i % instructions.len()] {
-                'L' => {
-                    current = &maps.get(current).ok_or("Failed to get the current node")?.left;
-                },
-                'R' => {
-                    current = &maps.get(current).ok_or("Failed to get the current node")?.right;
-                },
-                _ => return Err("Unknown instruction".to_string()),
-            }
+    Ok((Day8::parse(&read_input(file_path)?)?, Options { walk, max_steps, at, dot, analyze }))
+}
 
-            if current.ends_with("Z") {
-                let step = i - last;
-                if current_required_steps.contains(&step) {
-                    break 'calculate_step;
-                } else {
-                    current_required_steps.push(step);
-                    last = i;
-                }
-            }
-            i += 1;
-        }
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let (puzzle, options) = match run(&args) {
+        Ok(result) => result,
+        Err(error_message) => {
+            eprintln!("ERROR: {}", error_message);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    // The examples only have the nodes for one of the parts, so each part is reported alone
+    let mut exit_code = ExitCode::SUCCESS;
+    match puzzle.steps(&Walk { max_steps: options.max_steps, ..Walk::part1() }) {
+        Ok(result) => println!("Result of part 1: {result}"),
+        Err(error_message) => {
+            eprintln!("ERROR: {}", error_message);
+            exit_code = ExitCode::FAILURE;
+        },
+    }
+    match puzzle.steps(&Walk { max_steps: options.max_steps, ..Walk::part2() }) {
+        Ok(result) => println!("Result of part 2: {result}"),
+        Err(error_message) => {
+            eprintln!("ERROR: {}", error_message);
+            exit_code = ExitCode::FAILURE;
+        },
+    }
 
-        every_required_steps.push(current_required_steps);
+    if let Some(walk) = &options.walk {
+        let walkers = if walk.mode == Mode::Single { "one walker" } else { "walkers together" };
+        match puzzle.steps(walk) {
+            Ok(result) => println!("Steps from {} to {} with {walkers}: {result}", walk.starts, walk.ends),
+            Err(error_message) => {
+                eprintln!("ERROR: {}", error_message);
+                exit_code = ExitCode::FAILURE;
+            },
+        }
     }
 
-    println!("every required steps: {every_required_steps:?}");
+    // The starts and ends of the other outputs are the ghosts' unless others are given
+    let walk = options.walk.unwrap_or_else(Walk::part2);
 
-    Ok(0)
-}
+    // Where every start is after that many steps, without walking all of them
+    if let Some(steps) = options.at {
+        let jumps = Jumps::new(&puzzle);
+        let ends = puzzle.flags(&walk.ends);
+        for start in puzzle.select(&walk.starts) {
+            let name = puzzle.network.name(start);
+            let position = puzzle.network.name(jumps.position_after(start, steps));
+            match jumps.cycle(start, &ends).next_end(steps) {
+                Some(end) => println!("{name} is on {position} after {steps} steps, the next end node is at step {end}"),
+                None => println!("{name} is on {position} after {steps} steps and never reaches an end node again"),
+            }
+        }
+    }
 
-fn main() {
-    let file_path = env::args().skip(1).next().expect("Please provide an input file path");
-    match solve_file(&file_path) {
-        Ok(result) => {
-            println!("Result: {result}");
-        },
-        Err(error_message) => eprintln!("ERROR: {}", error_message),
+    if options.analyze {
+        let names = |ids: &[u32]| ids.iter().map(|&id| puzzle.network.name(id)).collect::<Vec<&str>>().join(" ");
+        let analysis = puzzle.analyze(&walk);
+        let cycles: Vec<&Vec<u32>> = analysis.components.iter().filter(|component| component.len() > 1).collect();
+        println!("{} strongly connected components, {} of them with more than one node:", analysis.components.len(), cycles.len());
+        for component in cycles {
+            println!("    {}", names(component));
+        }
+        for (start, walked, graph) in &analysis.reached {
+            println!("{} gets to [{}] and could get to [{}]", puzzle.network.name(*start), names(walked), names(graph));
+        }
+        println!("Never visited ({}): {}", analysis.unvisited.len(), names(&analysis.unvisited));
     }
 
+    if let Some(path) = &options.dot {
+        if let Err(source) = fs::write(path, puzzle.to_dot(&walk)) {
+            eprintln!("ERROR: {}", Error::Io { path: path.clone(), source });
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+    return exit_code;
 }
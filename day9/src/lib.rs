@@ -0,0 +1,449 @@
+use std::fmt;
+use std::io::BufRead;
+
+use aoc_common::{lines, read_lines, BigInt, Result, Rng, Solution};
+
+// The arithmetic of the extrapolation. i128 returns None when it overflows so the slower
+// BigInt can take over, BigInt never does.
+trait Integer: Clone {
+    fn from_i128(value: i128) -> Self;
+    fn is_zero(&self) -> bool;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    // `self` has to be a multiple of `divisor`
+    fn exact_div(&self, divisor: i64) -> Self;
+}
+
+impl Integer for i128 {
+    fn from_i128(value: i128) -> Self {
+        value
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        i128::checked_add(*self, *other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        i128::checked_sub(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        i128::checked_mul(*self, *other)
+    }
+
+    fn exact_div(&self, divisor: i64) -> Self {
+        self / divisor as i128
+    }
+}
+
+impl Integer for BigInt {
+    fn from_i128(value: i128) -> Self {
+        BigInt::from(value)
+    }
+
+    fn is_zero(&self) -> bool {
+        BigInt::is_zero(self)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn exact_div(&self, divisor: i64) -> Self {
+        self.div_rem(&BigInt::from(divisor)).0
+    }
+}
+
+// The first and last value of every row of differences, until a row is all zeros. The first
+// row is the sequence. Only one row is kept at a time so long sequences don't need the
+// whole triangle.
+fn edges<T: Integer>(sequence: &[T]) -> Option<Vec<(T, T)>> {
+    let mut result = Vec::new();
+    let mut row = sequence.to_vec();
+    while !row.iter().all(T::is_zero) {
+        result.push((row[0].clone(), row[row.len() - 1].clone()));
+        for i in 0..row.len() - 1 {
+            row[i] = row[i + 1].checked_sub(&row[i])?;
+        }
+        row.pop();
+    }
+    Some(result)
+}
+
+// Newton's forward formula from the first values and the backward one from the last values,
+// the differences below the last row are zero. `steps` past the last value is the sum of
+// C(steps + j - 1, j) times the last value of row j, and `steps` before the first value the
+// same with the first values and alternating signs.
+fn extrapolate_edges<T: Integer>(edges: &[(T, T)], steps: i64) -> Option<T> {
+    let k = T::from_i128(steps.unsigned_abs() as i128);
+    let mut result = T::from_i128(0);
+    let mut binomial = T::from_i128(1);
+    for (j, (first, last)) in edges.iter().enumerate() {
+        if j > 0 {
+            let top = k.checked_add(&T::from_i128(j as i128 - 1))?;
+            binomial = binomial.checked_mul(&top)?.exact_div(j as i64);
+        }
+        if steps >= 0 {
+            result = result.checked_add(&binomial.checked_mul(last)?)?;
+        } else if j % 2 == 0 {
+            result = result.checked_add(&binomial.checked_mul(first)?)?;
+        } else {
+            result = result.checked_sub(&binomial.checked_mul(first)?)?;
+        }
+    }
+    Some(result)
+}
+
+// The value `steps` past the last one of the sequence, or before the first one when `steps` is
+// negative. It's worked out in i128 when everything fits and with BigInt otherwise.
+pub fn extrapolate(sequence: &[BigInt], steps: i64) -> BigInt {
+    let small: Option<Vec<i128>> = sequence.iter().map(BigInt::to_i128).collect();
+    if let Some(value) = small.and_then(|small| extrapolate_edges(&edges(&small)?, steps)) {
+        return BigInt::from(value);
+    }
+    let edges = edges(sequence).expect("BigInt doesn't overflow");
+    return extrapolate_edges(&edges, steps).expect("BigInt doesn't overflow");
+}
+
+pub fn next_value(sequence: &[BigInt]) -> BigInt {
+    extrapolate(sequence, 1)
+}
+
+pub fn previous_value(sequence: &[BigInt]) -> BigInt {
+    extrapolate(sequence, -1)
+}
+
+// A fraction in lowest terms, the denominator is always positive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fraction {
+    pub numerator: BigInt,
+    pub denominator: BigInt,
+}
+
+impl Fraction {
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Self {
+        let mut divisor = numerator.gcd(&denominator);
+        if denominator.is_negative() {
+            divisor = -divisor;
+        }
+        Self { numerator: numerator.div_rem(&divisor).0, denominator: denominator.div_rem(&divisor).0 }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == BigInt::from(1)
+    }
+}
+
+impl fmt::Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+// The polynomial of the smallest degree that goes through every value of a sequence, with the
+// first value at n = 0. It's kept in Newton's form, the first value of every row of
+// differences, which gives exact integers at any n, and in the usual form for showing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    differences: Vec<BigInt>,
+    // Of n^0 first, the last one is never zero
+    pub coefficients: Vec<Fraction>,
+}
+
+impl Polynomial {
+    pub fn fit(sequence: &[BigInt]) -> Self {
+        let differences: Vec<BigInt> = edges(sequence).expect("BigInt doesn't overflow")
+            .into_iter()
+            .map(|(first, _)| first)
+            .collect();
+
+        // f(n) is the sum of the differences times C(n, j) = n (n - 1) ... (n - j + 1) / j!. The
+        // falling products have integer coefficients, so everything is summed over the biggest
+        // j! and only divided at the end.
+        let degree = differences.len().saturating_sub(1);
+        let mut factorials = vec![BigInt::from(1)];
+        for j in 1..=degree {
+            factorials.push(&factorials[j - 1] * &BigInt::from(j as i64));
+        }
+        let mut numerators = vec![BigInt::zero(); differences.len()];
+        let mut falling = vec![BigInt::from(1)];
+        for (j, difference) in differences.iter().enumerate() {
+            if j > 0 {
+                // Times (n - j + 1)
+                let root = BigInt::from(j as i64 - 1);
+                let mut next = vec![BigInt::zero(); falling.len() + 1];
+                for (power, coefficient) in falling.iter().enumerate() {
+                    next[power + 1] = &next[power + 1] + coefficient;
+                    next[power] = &next[power] - &(coefficient * &root);
+                }
+                falling = next;
+            }
+            let scale = difference * &factorials[degree].div_rem(&factorials[j]).0;
+            for (power, coefficient) in falling.iter().enumerate() {
+                numerators[power] = &numerators[power] + &(coefficient * &scale);
+            }
+        }
+
+        let coefficients = numerators.into_iter()
+            .map(|numerator| Fraction::new(numerator, factorials[degree].clone()))
+            .collect();
+        Self { differences, coefficients }
+    }
+
+    // The zero polynomial has degree 0 too
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    // The value at any n, before the first value when it's negative. The generalised C(n, j)
+    // is an integer for every integer n, so this stays exact.
+    pub fn evaluate(&self, n: i64) -> BigInt {
+        let n = BigInt::from(n);
+        let mut result = BigInt::zero();
+        let mut binomial = BigInt::from(1);
+        for (j, difference) in self.differences.iter().enumerate() {
+            if j > 0 {
+                binomial = (&binomial * &(&n - &BigInt::from(j as i64 - 1))).div_rem(&BigInt::from(j as i64)).0;
+            }
+            result = &result + &(difference * &binomial);
+        }
+        return result;
+    }
+}
+
+// Like `3/2 n^2 - n + 4`
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (power, coefficient) in self.coefficients.iter().enumerate().rev() {
+            if coefficient.numerator.is_zero() {
+                continue;
+            }
+            let sign = if coefficient.numerator.is_negative() { "-" } else { "+" };
+            match (first, sign) {
+                (true, "-") => write!(f, "-")?,
+                (true, _) => (),
+                (false, _) => write!(f, " {sign} ")?,
+            }
+            first = false;
+
+            // 3n^2 but 3/2 n^2, and no 1 in front of n
+            let magnitude = Fraction { numerator: coefficient.numerator.abs(), denominator: coefficient.denominator.clone() };
+            if !magnitude.is_integer() {
+                write!(f, "{magnitude}{}", if power > 0 { " " } else { "" })?;
+            } else if power == 0 || magnitude.numerator != BigInt::from(1) {
+                write!(f, "{magnitude}")?;
+            }
+            match power {
+                0 => (),
+                1 => write!(f, "n")?,
+                _ => write!(f, "n^{power}")?,
+            }
+        }
+        if first {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+// Random sequences for `aoc gen`, the values of polynomials with small integer coefficients
+// so the differences reach zero after at most `max_degree + 1` rows
+pub fn random_sequences(rng: &mut Rng, amount: usize, length: usize, max_degree: usize) -> Vec<Vec<i32>> {
+    let mut sequences = Vec::new();
+    for _ in 0..amount {
+        let coefficients: Vec<i64> = (0..=rng.below(max_degree as u64 + 1)).map(|_| rng.range(-5..6)).collect();
+        let sequence = (0..length as i64)
+            .map(|x| coefficients.iter().rev().fold(0, |value, coefficient| value * x + coefficient) as i32)
+            .collect();
+        sequences.push(sequence);
+    }
+    return sequences;
+}
+
+pub fn sequences_to_input(sequences: &[Vec<i32>]) -> String {
+    let mut result = String::new();
+    for sequence in sequences {
+        let values: Vec<String> = sequence.iter().map(|value| value.to_string()).collect();
+        result += &values.join(" ");
+        result.push('\n');
+    }
+    return result;
+}
+
+// Calls `f` with the line number and values of every sequence, one line at a time
+pub fn for_each_sequence(reader: impl BufRead, path: &str, mut f: impl FnMut(usize, Vec<BigInt>) -> Result<()>) -> Result<()> {
+    read_lines(reader, path, |line| f(line.number, line.numbers(line.text)?))
+}
+
+// Both parts one sequence at a time, `steps` past the end and before the start
+pub fn solve_reader(reader: impl BufRead, path: &str, steps: i64) -> Result<(BigInt, BigInt)> {
+    let mut part_1 = BigInt::zero();
+    let mut part_2 = BigInt::zero();
+    for_each_sequence(reader, path, |_, sequence| {
+        part_1 = &part_1 + &extrapolate(&sequence, steps);
+        part_2 = &part_2 + &extrapolate(&sequence, -steps);
+        Ok(())
+    })?;
+    Ok((part_1, part_2))
+}
+
+pub struct Day9 {
+    pub sequences: Vec<Vec<BigInt>>,
+}
+
+impl Day9 {
+    pub fn polynomials(&self) -> Vec<Polynomial> {
+        self.sequences.iter().map(|sequence| Polynomial::fit(sequence)).collect()
+    }
+}
+
+impl Solution for Day9 {
+    type Part1 = BigInt;
+    type Part2 = BigInt;
+
+    fn parse(input: &str) -> Result<Self> {
+        let mut sequences: Vec<Vec<BigInt>> = Vec::new();
+        for line in lines(input) {
+            sequences.push(line.numbers(line.text)?);
+        }
+        Ok(Self { sequences })
+    }
+
+    fn part1(&self) -> Result<BigInt> {
+        Ok(self.sequences.iter().map(|sequence| next_value(sequence)).sum())
+    }
+
+    fn part2(&self) -> Result<BigInt> {
+        Ok(self.sequences.iter().map(|sequence| previous_value(sequence)).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{check, shrink_i64, shrink_vec};
+
+    use super::*;
+
+    fn polynomial(coefficients: &[BigInt], x: i64) -> BigInt {
+        coefficients.iter().rev().fold(BigInt::zero(), |value, coefficient| &(&value * &BigInt::from(x)) + coefficient)
+    }
+
+    #[test]
+    fn example() {
+        let puzzle = Day9::parse(include_str!("../example.txt")).unwrap();
+        assert_eq!(puzzle.part1().unwrap(), BigInt::from(114));
+        assert_eq!(puzzle.part2().unwrap(), BigInt::from(2));
+    }
+
+    #[test]
+    fn hundreds_of_terms() {
+        let mut rng = Rng::new(9);
+        let coefficients: Vec<BigInt> = (0..30).map(|_| BigInt::from(rng.range(-1000..1000))).collect();
+        let sequence: Vec<BigInt> = (0..300).map(|x| polynomial(&coefficients, x)).collect();
+        // The last values are around 10^77, far past i128
+        assert!(sequence[299].to_i128().is_none());
+        for steps in [1, 2, 17, 1000] {
+            assert_eq!(extrapolate(&sequence, steps), polynomial(&coefficients, 299 + steps));
+            assert_eq!(extrapolate(&sequence, -steps), polynomial(&coefficients, -steps));
+        }
+
+        let fitted = Polynomial::fit(&sequence);
+        assert_eq!(fitted.degree(), 29);
+        let expected: Vec<Fraction> = coefficients.iter().map(|c| Fraction::new(c.clone(), BigInt::from(1))).collect();
+        assert_eq!(fitted.coefficients, expected);
+    }
+
+    #[test]
+    fn example_polynomials() {
+        let puzzle = Day9::parse(include_str!("../example.txt")).unwrap();
+        let formulas: Vec<String> = puzzle.polynomials().iter().map(|polynomial| polynomial.to_string()).collect();
+        assert_eq!(formulas, ["3n", "1/2 n^2 + 3/2 n + 1", "1/3 n^3 - n^2 + 11/3 n + 10"]);
+        assert_eq!(Polynomial::fit(&[]).to_string(), "0");
+        assert_eq!(Polynomial::fit(&[BigInt::from(-1), BigInt::from(-2)]).to_string(), "-n - 1");
+    }
+
+    #[test]
+    fn polynomial_goes_through_the_sequence() {
+        check(
+            |rng| (0..rng.below(10)).map(|_| rng.range(-1000..1000)).collect::<Vec<i64>>(),
+            |sequence| shrink_vec(sequence, |&value| shrink_i64(value)),
+            |sequence| {
+                let sequence: Vec<BigInt> = sequence.iter().map(|&value| BigInt::from(value)).collect();
+                let fitted = Polynomial::fit(&sequence);
+                // The usual form gives the same values as Newton's, summed as fractions
+                let usual_form = |n: i64| fitted.coefficients.iter().rev().fold(Fraction::new(BigInt::zero(), BigInt::from(1)), |value, coefficient| {
+                    let times_n = &value.numerator * &BigInt::from(n);
+                    Fraction::new(
+                        &(&times_n * &coefficient.denominator) + &(&coefficient.numerator * &value.denominator),
+                        &value.denominator * &coefficient.denominator,
+                    )
+                });
+                let last = sequence.len() as i64 - 1;
+                fitted.degree() <= sequence.len().saturating_sub(1)
+                    && sequence.iter().enumerate().all(|(n, value)| fitted.evaluate(n as i64) == *value)
+                    && (1..4).all(|steps| fitted.evaluate(last + steps) == extrapolate(&sequence, steps)
+                        && fitted.evaluate(-steps) == extrapolate(&sequence, -steps))
+                    && (-3..12).all(|n| usual_form(n) == Fraction::new(fitted.evaluate(n), BigInt::from(1)))
+            },
+        );
+    }
+
+    #[test]
+    fn falls_back_when_i128_overflows() {
+        // Both values fit in i128 but their difference doesn't
+        let big = BigInt::from(1i128 << 126);
+        let sequence = vec![-&big, big.clone()];
+        assert_eq!(extrapolate(&sequence, 1), &BigInt::from(3) * &big);
+        assert_eq!(extrapolate(&sequence, -2), &BigInt::from(-5) * &big);
+    }
+
+    #[test]
+    fn steps_match_extending_one_at_a_time() {
+        check(
+            |rng| {
+                let sequence = (0..rng.below(12)).map(|_| rng.range(-1000..1000)).collect::<Vec<i64>>();
+                (sequence, rng.range(1..6))
+            },
+            |(sequence, steps)| {
+                let mut result: Vec<(Vec<i64>, i64)> = shrink_vec(sequence, |&value| shrink_i64(value)).into_iter()
+                    .map(|sequence| (sequence, *steps))
+                    .collect();
+                result.extend(shrink_i64(*steps).into_iter().filter(|&steps| steps > 0).map(|steps| (sequence.clone(), steps)));
+                result
+            },
+            |(sequence, steps)| {
+                let sequence: Vec<BigInt> = sequence.iter().map(|&value| BigInt::from(value)).collect();
+                let mut ahead = sequence.clone();
+                let mut behind = sequence.clone();
+                for _ in 0..*steps {
+                    ahead.push(next_value(&ahead));
+                    behind.insert(0, previous_value(&behind));
+                }
+                // The i128 and BigInt ways agree when nothing overflows
+                let small: Vec<i128> = sequence.iter().map(|value| value.to_i128().unwrap()).collect();
+                let small = extrapolate_edges(&edges(&small).unwrap(), *steps).unwrap();
+                extrapolate(&sequence, *steps) == ahead[ahead.len() - 1]
+                    && extrapolate(&sequence, -steps) == behind[0]
+                    && extrapolate_edges(&edges(&sequence).unwrap(), *steps) == Some(BigInt::from(small))
+            },
+        );
+    }
+}
@@ -1,26 +1,54 @@
 use std::env;
-use std::fs;
+use std::process::ExitCode;
 
-fn solve_file(file_path: &str) -> Result<(i32, i32), String> {
-    let file_content = fs::read_to_string(file_path)
-        .map_err(|e| e.to_string())?;
+use aoc_common::{input_path, open_input, BigInt, Error, Result};
+use day9::{extrapolate, for_each_sequence, Polynomial};
 
-    let mut sequences: Vec<Vec<i32>> = Vec::new();
-    for line in file_content.lines() {
-        let mut sequence = Vec::<i32>::new();
-        for num in line.trim().split_whitespace() {
-            sequence.push(num.parse().map_err(|_| "Failed to parse number into i32".to_string())?);
+const USAGE: &str = "Usage: day9 <input|-> [--steps <k>] [--explain]";
+
+fn run(args: &[String]) -> Result<(BigInt, BigInt)> {
+    let file_path = input_path(args).map_err(|e| Error::validation(format!("{e}\n{USAGE}")))?;
+
+    let mut steps = 1;
+    let mut explain = false;
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--explain" => explain = true,
+            "--steps" => {
+                match options.next().map(|value| value.parse::<i64>()) {
+                    Some(Ok(value)) if value > 0 => steps = value,
+                    _ => return Err(Error::validation("--steps expects a number of steps above 0")),
+                }
+            },
+            _ => return Err(Error::validation(format!("Unknown option {option}\n{USAGE}"))),
         }
-        sequences.push(sequence);
     }
-    println!("{sequences:?}");
-    return Ok((12, 34));
+
+    let mut part_1 = BigInt::zero();
+    let mut part_2 = BigInt::zero();
+    for_each_sequence(open_input(file_path)?, file_path, |number, sequence| {
+        if explain {
+            let polynomial = Polynomial::fit(&sequence);
+            println!("Line {number}: f(n) = {polynomial} (degree {})", polynomial.degree());
+        }
+        part_1 = &part_1 + &extrapolate(&sequence, steps);
+        part_2 = &part_2 + &extrapolate(&sequence, -steps);
+        Ok(())
+    })?;
+    Ok((part_1, part_2))
 }
 
-fn main() {
-    let file_path = env::args().skip(1).next().expect("Expecting a file path as an arguments");
-    match solve_file(&file_path) {
-        Ok((part1, part2)) => println!("The answers is ({}, {})", part1, part2),
-        Err(msg) => eprintln!("ERROR: {}", msg),
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    match run(&args) {
+        Ok((part1, part2)) => {
+            println!("The answers is ({}, {})", part1, part2);
+            ExitCode::SUCCESS
+        },
+        Err(msg) => {
+            eprintln!("ERROR: {}", msg);
+            ExitCode::FAILURE
+        },
     }
 }